@@ -44,10 +44,10 @@ impl Cli {
         let mut r = Row::empty();
 
         for c in row {
-            let mut c = if let ui::Cell::Float(value) = c {
-                Cell::new(&format!("{:>6.2}%", value))
-            } else {
-                Cell::new(&String::from(c))
+            let mut c = match c {
+                ui::Cell::Float(value) => Cell::new(&format!("{:>6.2}%", value)),
+                ui::Cell::Decimal(value) => Cell::new(&format!("{:.2}", value)),
+                _ => Cell::new(&String::from(c)),
             };
             if highlight {
                 c = c.style_spec("bFy");
@@ -97,4 +97,34 @@ impl ui::Ui for Cli {
         }
         false
     }
+
+    /// Ask the user which of the given choices they prefer until they answer with 1 or 2, and
+    /// return whether it's the first.
+    fn prompt_comparison(&self, first: &str, second: &str) -> bool {
+        loop {
+            print!("Do you prefer (1) {} or (2) {}? (1/2) ", first, second);
+            io::stdout().flush().unwrap();
+            let line = io::stdin().lock().lines().next().unwrap().unwrap();
+            match line.trim() {
+                "1" => return true,
+                "2" => return false,
+                _ => {}
+            }
+        }
+    }
+
+    /// Ask the user how well they recalled the given choice until they answer with a grade.
+    fn prompt_grade(&self, choice: &str) -> u8 {
+        loop {
+            print!(
+                "How well did you recall {}? (0 = not at all, 5 = perfectly) ",
+                choice
+            );
+            io::stdout().flush().unwrap();
+            let line = io::stdin().lock().lines().next().unwrap().unwrap();
+            if let Ok(grade @ 0..=5) = line.trim().parse::<u8>() {
+                return grade;
+            }
+        }
+    }
 }
@@ -23,6 +23,7 @@ use std::{
     path::Path,
 };
 
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 use serde::{Deserialize, Serialize};
 
 /// Return the user's config as a BTreeMap.
@@ -73,12 +74,111 @@ pub fn write_config(
 #[serde(rename_all = "snake_case")]
 #[serde(tag = "model")]
 pub enum ConfigCategory {
+    /// The Bandit variant learns which choices the user likes from their answers. Each choice
+    /// counts how many times it has been accepted and rejected, and choices are picked with
+    /// [Thompson sampling](https://en.wikipedia.org/wiki/Thompson_sampling) from the Beta
+    /// distributions that those counts describe. Choices that keep getting rejected fade, and
+    /// choices that keep getting accepted rise.
+    ///
+    /// # Attributes
+    ///
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Bandit {
+        choices: Vec<BanditChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Bundle variant picks a set of its choices whose total cost stays within a budget,
+    /// rather than picking one of them. Choices are added to the set one at a time with a
+    /// weighted distribution, from the choices that still fit within what is left of the budget,
+    /// until none of them fit. The category doesn't keep any state.
+    ///
+    /// # Attributes
+    ///
+    /// * `budget` - The most that the costs of the set's choices may add up to.
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Bundle {
+        budget: f64,
+        choices: Vec<BundleChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Composite variant uses a weighted distribution to pick items, with each item's weight
+    /// being combined from several factors. This allows a category to mix the ideas of other
+    /// models, such as a static weight that is boosted for choices that haven't been picked in a
+    /// while.
+    ///
+    /// # Attributes
+    ///
+    /// * `combine` - How the factors are combined into a weight.
+    /// * `factors` - The factors that make up each choice's weight.
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Composite {
+        #[serde(default)]
+        combine: Combine,
+        factors: Vec<CompositeFactor>,
+        choices: Vec<CompositeChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
     /// The Even variant picks from its choices with even distribution.
     ///
     /// # Attributes
     ///
     /// * `choices` - The list of choices to pick from.
-    Even { choices: Vec<String> },
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Even {
+        choices: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Deck variant works like a shuffled deck of cards. Choices are drawn with even
+    /// distribution from the ones that are still in the deck, and accepted choices are removed
+    /// from it. Once every choice has been drawn, the deck is reshuffled with all of the choices.
+    ///
+    /// # Attributes
+    ///
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Deck {
+        choices: Vec<DeckChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Dice variant rolls dice rather than picking from a list of choices. The category
+    /// doesn't keep any state.
+    ///
+    /// # Attributes
+    ///
+    /// * `roll` - The dice to roll, such as `2d6+1`. This is made of dice and whole numbers that
+    ///   are joined by `+` or `-`. Dice are written as the number of dice, which defaults to 1,
+    ///   followed by `d` and the number of sides that each die has.
+    Dice { roll: String },
+    /// The FloatRange variant picks a number between `min` and `max`, including both of them,
+    /// rather than picking from a list of choices. The category doesn't keep any state.
+    ///
+    /// # Attributes
+    ///
+    /// * `min` - The smallest number that can be picked.
+    /// * `max` - The largest number that can be picked.
+    /// * `step` - If this is set, only `min` plus multiples of `step` can be picked.
+    /// * `distribution` - How likely each number in the range is.
+    FloatRange {
+        min: f64,
+        max: f64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        step: Option<f64>,
+        #[serde(default)]
+        distribution: RangeDistribution,
+    },
     /// The Gaussian variant uses a
     /// [Gaussian distribution](https://en.wikipedia.org/wiki/Normal_distribution) to prefer choices
     /// near the beginning of the list of choices over those at the end. Once a choice has been
@@ -88,11 +188,58 @@ pub enum ConfigCategory {
     ///
     /// * `stddev_scaling_factor` - This is used to derive the standard deviation; the standard
     ///   deviation is the length of the list of choices, divided by this scaling factor.
+    /// * `mean` - Where the chances peak. Each choice covers one unit of the number line, starting
+    ///   at 0 with the first choice. If this is `None`, the chances peak at 0.
+    /// * `last_item_probability` - If this is set, the standard deviation is instead derived so
+    ///   that the last item of the list has this chance of being chosen.
     /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
     Gaussian {
         #[serde(default = "default_stddev_scaling_factor")]
         stddev_scaling_factor: f64,
-        choices: Vec<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mean: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        last_item_probability: Option<f64>,
+        choices: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Geometric variant prefers choices near the beginning of the list of choices over those
+    /// at the end, with each choice having `1 - p` times the chance of the choice before it, like
+    /// a [geometric distribution](https://en.wikipedia.org/wiki/Geometric_distribution). Once a
+    /// choice has been accepted, it is moved to the end of the list.
+    ///
+    /// # Attributes
+    ///
+    /// * `p` - How strongly the beginning of the list is preferred, between 0 and 1.
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Geometric {
+        #[serde(default = "default_p")]
+        p: f64,
+        choices: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The IntegerRange variant picks a whole number between `min` and `max`, including both of
+    /// them, rather than picking from a list of choices. The category doesn't keep any state.
+    ///
+    /// # Attributes
+    ///
+    /// * `min` - The smallest number that can be picked.
+    /// * `max` - The largest number that can be picked.
+    /// * `step` - Only `min` plus multiples of `step` can be picked.
+    /// * `distribution` - How likely each number in the range is.
+    IntegerRange {
+        min: i64,
+        max: i64,
+        #[serde(default = "default_step")]
+        step: u64,
+        #[serde(default)]
+        distribution: RangeDistribution,
     },
     /// The Inventory variant uses a weighted distribution to pick items, with each items chances
     /// being tied to how many tickets it has. When a choice is accepted, that choice's ticket
@@ -101,7 +248,79 @@ pub enum ConfigCategory {
     /// # Attributes
     ///
     /// * `choices` - The list of choices to pick from.
-    Inventory { choices: Vec<InventoryChoice> },
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Inventory {
+        choices: Vec<InventoryChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Lfu variant picks the Least Frequently Used item from the list of choices. Each choice
+    /// counts how many times it has been accepted, and choices with lower counts are preferred.
+    /// When a choice is accepted, its `count` is increased by 1.
+    ///
+    /// # Attributes
+    ///
+    /// * `exponent` - If this is `None`, the choice with the lowest count is always picked, and
+    ///   the next lowest if that choice is rejected. If it is set, choices are picked with a
+    ///   weighted distribution instead, with each choice's weight being 1 divided by one more than
+    ///   its count, raised to this power.
+    /// * `decay` - If this is set, every choice's count is multiplied by this factor, between 0
+    ///   and 1, before an accepted choice's count is increased, so that old picks fade over time.
+    /// * `reset_at` - If this is set, every choice's count is reset to 0 once an accepted choice's
+    ///   count reaches this.
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    #[serde(rename = "lfu")]
+    Lfu {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        exponent: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        decay: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reset_at: Option<f64>,
+        choices: Vec<LfuChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Linear variant prefers choices near the beginning of the list of choices over those at
+    /// the end, with chances that fall in a straight line from the first choice to the last. The
+    /// last choice has a weight of 1, and each choice before it has 1 more. Once a choice has been
+    /// accepted, it is moved to the end of the list.
+    ///
+    /// # Attributes
+    ///
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Linear {
+        choices: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Loot variant rolls on a loot table. Each roll picks one of the choices with a weighted
+    /// distribution. Choices can hold their own weighted tables, which are rolled on in turn when
+    /// they come up, so that a roll can pick a tier of rarity first and then an item within that
+    /// tier. Choices that reroll their table roll it that many more times, ignoring the choices
+    /// that reroll it. The category doesn't keep any state.
+    ///
+    /// # Attributes
+    ///
+    /// * `rolls` - How many times to roll on the table.
+    /// * `guaranteed` - The names of items that are always dropped, on top of the rolls.
+    /// * `choices` - The table to roll on.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Loot {
+        #[serde(default = "default_rolls")]
+        rolls: u64,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        guaranteed: Vec<String>,
+        choices: Vec<LootChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
     /// The Lru variant picks the Least Recently Used item from the list of choices. The least
     /// recently used choice is found at the beginning of the list. Once a choice has been
     /// accepted, it is moved to the end of the list.
@@ -109,8 +328,14 @@ pub enum ConfigCategory {
     /// # Attributes
     ///
     /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
     #[serde(rename = "lru")]
-    Lru { choices: Vec<String> },
+    Lru {
+        choices: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
     /// The Lottery variant uses a weighted distribution to pick items, with each items chances
     /// being tied to how many tickets it has. When a choice is accepted, that choice's ticket
     /// count is set to 0, and every choice not chosen receives its weight in additional tickets.
@@ -118,13 +343,564 @@ pub enum ConfigCategory {
     /// # Attributes
     ///
     /// * `choices` - The list of choices to pick from.
-    Lottery { choices: Vec<LotteryChoice> },
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Lottery {
+        choices: Vec<LotteryChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Markov variant picks the next choice based on the choice that was accepted last. Each
+    /// choice lists weights for the choices that may follow it, and the next choice is picked with
+    /// a weighted distribution using the weights of the last accepted choice.
+    ///
+    /// # Attributes
+    ///
+    /// * `last` - The name of the choice that was accepted last, if any.
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Markov {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        last: Option<String>,
+        choices: Vec<MarkovChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Pairs variant splits its choices into random pairs, rather than picking some of them.
+    /// Pairs that haven't worked together for longer are more likely, and if there is an odd
+    /// number of choices, one of the choices that has sat out the fewest times sits out. Once a
+    /// set of pairs is accepted, `rounds` is increased by 1 and the pairs are recorded in each
+    /// choice's `last_paired` history.
+    ///
+    /// # Attributes
+    ///
+    /// * `rounds` - How many sets of pairs have been accepted.
+    /// * `choices` - The list of choices to pair up.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Pairs {
+        #[serde(default)]
+        rounds: u64,
+        choices: Vec<PairChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Quota variant keeps each choice's share of the picks close to a target share over the
+    /// long run. It always favors the choice that is furthest behind its target share, and then
+    /// the next furthest behind if that choice is rejected. When a choice is accepted, its `picks`
+    /// count is increased by 1.
+    ///
+    /// # Attributes
+    ///
+    /// * `tolerance` - Choices whose deficits are within this many picks of the largest deficit
+    ///   are treated as tied, and are picked from evenly.
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Quota {
+        #[serde(default)]
+        tolerance: f64,
+        choices: Vec<QuotaChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Rated variant uses a weighted distribution to pick items, with each item's chances
+    /// being tied to its [Elo rating](https://en.wikipedia.org/wiki/Elo_rating_system). The ratings
+    /// are found by asking the user to compare pairs of choices in a tournament.
+    ///
+    /// # Attributes
+    ///
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Rated {
+        choices: Vec<RatedChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Recency variant uses a weighted distribution to pick items, with each item's weight
+    /// growing with the time that has passed since it was last accepted. When a choice is
+    /// accepted, its `last_picked` time is set to the current time.
+    ///
+    /// # Attributes
+    ///
+    /// * `curve` - How a choice's weight grows with the time since it was last picked.
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Recency {
+        #[serde(default)]
+        curve: RecencyCurve,
+        choices: Vec<RecencyChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The SecretSanta variant assigns each of its choices another choice to give a gift to, so
+    /// that every choice gives exactly one gift and receives exactly one gift. Choices never give
+    /// to themselves or to the choices they exclude, and they don't repeat the assignments of the
+    /// most recent rounds. Once an assignment is accepted, it is added to the history.
+    ///
+    /// # Attributes
+    ///
+    /// * `avoid_rounds` - How many of the most recent rounds in the history shouldn't be repeated.
+    /// * `history` - The assignments of previous rounds, oldest first. Each round maps the names
+    ///   of the givers to the names of their recipients.
+    /// * `choices` - The participants.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    SecretSanta {
+        #[serde(default = "default_avoid_rounds")]
+        avoid_rounds: usize,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        history: Vec<BTreeMap<String, String>>,
+        choices: Vec<SecretSantaChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The SpacedRepetition variant picks study or practice items with
+    /// [spaced repetition](https://en.wikipedia.org/wiki/Spaced_repetition). Each choice is due for
+    /// review at some time, and choices are picked with a weighted distribution that prefers the
+    /// choices that are most overdue. Once a choice has been accepted, the user grades how well
+    /// they recalled it, and the choice is rescheduled with the
+    /// [SM-2](https://en.wikipedia.org/wiki/SuperMemo#Description_of_SM-2_algorithm) algorithm.
+    ///
+    /// # Attributes
+    ///
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    SpacedRepetition {
+        choices: Vec<SpacedRepetitionChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Stratified variant is made of named groups, each of which is a category with its own
+    /// model and choices. Rather than picking from a single list, it picks one item from each
+    /// group in turn, and each group's state is updated just as if it had been picked from on its
+    /// own.
+    ///
+    /// # Attributes
+    ///
+    /// * `groups` - The groups to pick from, in the order that they are picked from.
+    Stratified { groups: Vec<StratifiedGroup> },
+    /// The Teams variant splits its choices into teams, rather than picking some of them. The
+    /// teams are as close to the same size as they can be, and their total skill is balanced
+    /// while keeping the teams random.
+    ///
+    /// # Attributes
+    ///
+    /// * `teams` - How many teams to split the choices into.
+    /// * `team_size` - If `teams` isn't set, the choices are split into as many teams of this size
+    ///   as they need. If neither is set, the choices are split into 2 teams.
+    /// * `together` - Lists of choices that must be on the same team.
+    /// * `apart` - Lists of choices that must all be on different teams.
+    /// * `choices` - The list of choices to split into teams.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Teams {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        teams: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        team_size: Option<usize>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        together: Vec<Vec<String>>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        apart: Vec<Vec<String>>,
+        choices: Vec<TeamChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
     /// The Weighted variant is a simple weighted distribution.
     ///
     /// # Attributes
     ///
     /// * `choices` - The list of choices to pick from.
-    Weighted { choices: Vec<WeightedChoice> },
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Weighted {
+        choices: Vec<WeightedChoice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+    /// The Zipf variant prefers choices near the beginning of the list of choices over those at
+    /// the end, with the choice at position `k` (counting from 1) having a weight of `1 / k^s`,
+    /// like [Zipf's law](https://en.wikipedia.org/wiki/Zipf%27s_law). Once a choice has been
+    /// accepted, it is moved to the end of the list.
+    ///
+    /// # Attributes
+    ///
+    /// * `exponent` - The exponent `s`. Larger exponents prefer the beginning of the list more
+    ///   strongly.
+    /// * `choices` - The list of choices to pick from.
+    /// * `cooldown` - How long the choices are held back after they are accepted, unless they set
+    ///   their own cooldown.
+    Zipf {
+        #[serde(default = "default_exponent")]
+        exponent: f64,
+        choices: Vec<Choice>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        cooldown: Option<Cooldown>,
+    },
+}
+
+impl ConfigCategory {
+    /// Turn a weighted category into a rated category.
+    ///
+    /// The ratings are proportional to the weights, and are scaled so that the average rating is
+    /// the default rating, so the rated category picks with the same chances that the weighted
+    /// category did. Categories of other models are left alone.
+    ///
+    /// # Returns
+    ///
+    /// Whether the category is a rated category.
+    pub fn convert_to_rated(&mut self) -> bool {
+        if let ConfigCategory::Weighted { choices, cooldown } = self {
+            let mean = choices.iter().map(|c| c.weight).sum::<u64>() as f64 / choices.len() as f64;
+            let choices = std::mem::take(choices)
+                .into_iter()
+                .map(|c| RatedChoice {
+                    name: c.name,
+                    rating: if mean > 0.0 {
+                        c.weight as f64 / mean * default_rating()
+                    } else {
+                        default_rating()
+                    },
+                    comparisons: 0,
+                    options: c.options,
+                })
+                .collect();
+            *self = ConfigCategory::Rated {
+                choices,
+                cooldown: *cooldown,
+            };
+        }
+
+        matches!(self, ConfigCategory::Rated { .. })
+    }
+}
+
+/// This trait gives the engine access to the fields that every kind of choice has, regardless of
+/// which model it belongs to.
+pub trait ConfigChoice {
+    /// Return the name of the choice.
+    fn name(&self) -> &str;
+
+    /// Return the options of the choice.
+    fn options(&self) -> &ChoiceOptions;
+
+    /// Return the options of the choice, so that they can be modified.
+    fn options_mut(&mut self) -> &mut ChoiceOptions;
+}
+
+/// Options that every kind of choice accepts, regardless of which model it belongs to.
+///
+/// # Attributes
+///
+/// * `category` - If this is set, accepting this choice will go on to pick an item from the
+///   category with this name.
+/// * `tags` - Labels that can be used to filter which choices are offered.
+/// * `schedule` - If this is set, the choice is only offered at the times the schedule allows.
+/// * `cooldown` - How long the choice is held back after it is accepted. This overrides the
+///   category's cooldown.
+/// * `cooling` - How much longer the choice is being held back for. rpick keeps this up to date.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ChoiceOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schedule: Option<Schedule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooldown: Option<Cooldown>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cooling: Option<Cooling>,
+}
+
+/// Describes how long a choice is held back after it is accepted.
+///
+/// If more than one of these are set, the choice is held back until all of them have passed.
+///
+/// # Attributes
+///
+/// * `picks` - How many picks from the category must happen before the choice is offered again.
+/// * `days` - How many days must pass before the choice is offered again.
+/// * `hours` - How many hours must pass before the choice is offered again, in addition to the
+///   days.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Cooldown {
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub picks: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub days: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub hours: u64,
+}
+
+/// Describes how much longer a choice is being held back for after it was accepted.
+///
+/// # Attributes
+///
+/// * `picks` - How many more picks from the category must happen before the choice is offered
+///   again.
+/// * `until` - The time the choice can be offered again.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Cooling {
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub picks: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub until: Option<DateTime<FixedOffset>>,
+}
+
+/// Describes when a choice is available.
+///
+/// Each of the lists that is not empty must have an entry that matches for the choice to be
+/// available. Empty lists don't restrict the choice at all.
+///
+/// # Attributes
+///
+/// * `days` - The days of the week the choice is available on.
+/// * `times` - The times of day the choice is available at.
+/// * `dates` - The ranges of dates the choice is available on.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schedule {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub days: Vec<Weekday>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub times: Vec<TimeRange>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dates: Vec<DateRange>,
+}
+
+impl Schedule {
+    /// Return whether the schedule allows the choice to be offered at the given local time.
+    pub fn includes(&self, at: NaiveDateTime) -> bool {
+        (self.days.is_empty() || self.days.contains(&at.weekday()))
+            && (self.times.is_empty() || self.times.iter().any(|t| t.includes(at.time())))
+            && (self.dates.is_empty() || self.dates.iter().any(|d| d.includes(at.date())))
+    }
+}
+
+/// A range of times of day, such as `11:00` to `14:30`.
+///
+/// The start is included in the range, and the end is not. If the end is before the start, the
+/// range continues past midnight.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TimeRange {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+impl TimeRange {
+    /// Return whether the given time of day falls within the range.
+    pub fn includes(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            self.start <= time || time < self.end
+        }
+    }
+}
+
+/// A range of dates, such as `2026-06-01` to `2026-08-31`. Both ends are included in the range.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DateRange {
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+}
+
+impl DateRange {
+    /// Return whether the given date falls within the range.
+    pub fn includes(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+}
+
+/// Represents an individual choice for the even, gaussian, and lru models.
+///
+/// In the config file, a choice can be written as a plain string if it has no options. Otherwise,
+/// it is written as an object with a `name` key, along with any of the keys from
+/// [`ChoiceOptions`].
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `options` - The options of the choice.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ChoiceRepr", into = "ChoiceRepr")]
+pub struct Choice {
+    pub name: String,
+    pub options: ChoiceOptions,
+}
+
+impl From<&str> for Choice {
+    fn from(name: &str) -> Self {
+        name.to_string().into()
+    }
+}
+
+impl From<String> for Choice {
+    fn from(name: String) -> Self {
+        Choice {
+            name,
+            options: ChoiceOptions::default(),
+        }
+    }
+}
+
+/// This is how a [`Choice`] is represented in the config file.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum ChoiceRepr {
+    Name(String),
+    Object {
+        name: String,
+        #[serde(flatten)]
+        options: ChoiceOptions,
+    },
+}
+
+impl From<ChoiceRepr> for Choice {
+    fn from(repr: ChoiceRepr) -> Self {
+        match repr {
+            ChoiceRepr::Name(name) => name.into(),
+            ChoiceRepr::Object { name, options } => Choice { name, options },
+        }
+    }
+}
+
+impl From<Choice> for ChoiceRepr {
+    fn from(choice: Choice) -> Self {
+        if choice.options == ChoiceOptions::default() {
+            ChoiceRepr::Name(choice.name)
+        } else {
+            ChoiceRepr::Object {
+                name: choice.name,
+                options: choice.options,
+            }
+        }
+    }
+}
+
+/// Represents an individual choice for the bandit model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `accepted` - How many times the user has accepted this choice.
+/// * `rejected` - How many times the user has rejected this choice.
+/// * `options` - The options of the choice.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BanditChoice {
+    pub name: String,
+    #[serde(default)]
+    pub accepted: u64,
+    #[serde(default)]
+    pub rejected: u64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the bundle model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `cost` - How much of the budget the choice uses up.
+/// * `weight` - How much chance this choice has of being added to the set, relative to the other
+///   choices.
+/// * `options` - The options of the choice.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct BundleChoice {
+    pub name: String,
+    pub cost: f64,
+    #[serde(default = "default_weight")]
+    pub weight: u64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Defines how the factors of a composite category are combined into a weight.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Combine {
+    /// The weight is the sum of the factors.
+    Add,
+    /// The weight is the product of the factors.
+    #[default]
+    Multiply,
+}
+
+/// Represents an individual choice for the composite model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `weight` - The choice's static weight, which is used by the weight factor.
+/// * `tickets` - The current number of tickets the choice has, which is used by the lottery
+///   factor.
+/// * `options` - The options of the choice.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CompositeChoice {
+    pub name: String,
+    #[serde(default = "default_weight")]
+    pub weight: u64,
+    #[serde(default = "default_weight")]
+    pub tickets: u64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// A factor that contributes to the weights of the choices in a composite category.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "factor")]
+pub enum CompositeFactor {
+    /// The factor is the choice's static weight.
+    Weight,
+    /// The factor decays with the choice's position in the list, like the lru model. The first
+    /// choice's factor is 1, and each following choice's factor is `decay` times the one before
+    /// it. Accepted choices are moved to the end of the list.
+    #[serde(rename = "lru")]
+    Lru {
+        #[serde(default = "default_decay")]
+        decay: f64,
+    },
+    /// The factor is the choice's ticket count, like the lottery model. When a choice is
+    /// accepted, its tickets are set to `reset`, and every eligible choice not chosen receives
+    /// `increment` additional tickets.
+    Lottery {
+        #[serde(default = "default_weight")]
+        increment: u64,
+        #[serde(default = "default_reset")]
+        reset: u64,
+    },
+    /// The factor is the product of the boosts of the choice's tags. Tags that aren't listed in
+    /// `boosts` don't change the factor, so choices without any boosted tags have a factor of 1.
+    Tags { boosts: BTreeMap<String, f64> },
+}
+
+/// Represents an individual choice for the deck model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `drawn` - Whether the choice has been drawn from the deck since it was last reshuffled.
+/// * `options` - The options of the choice.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DeckChoice {
+    pub name: String,
+    #[serde(default)]
+    pub drawn: bool,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
 }
 
 /// Represents an individual choice for the inventory model.
@@ -133,11 +909,57 @@ pub enum ConfigCategory {
 ///
 /// * `name` - The name of the choice.
 /// * `tickets` - The current number of tickets the choice has.
+/// * `options` - The options of the choice.
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct InventoryChoice {
     pub name: String,
     #[serde(default = "default_weight")]
     pub tickets: u64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the lfu model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `count` - How many times the choice has been accepted, less any decay.
+/// * `options` - The options of the choice.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct LfuChoice {
+    pub name: String,
+    #[serde(default)]
+    pub count: f64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the loot model, which is also used for the entries of the
+/// tables that choices hold.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `weight` - How much chance this choice has of being rolled, relative to the other choices in
+///   its table.
+/// * `reroll` - If this is more than 0, rolling this choice rolls the table it is in this many more
+///   times instead of dropping an item.
+/// * `table` - If this isn't empty, rolling this choice rolls on this table instead of dropping an
+///   item.
+/// * `options` - The options of the choice. These only apply to the choices of the category, and
+///   not to the entries of their tables.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct LootChoice {
+    pub name: String,
+    #[serde(default = "default_weight")]
+    pub weight: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub reroll: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub table: Vec<LootChoice>,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
 }
 
 /// Represents an individual choice for the lottery model.
@@ -160,6 +982,215 @@ pub struct LotteryChoice {
     /// The number of tickets that will be added to `tickets` each time this choice is not picked.
     #[serde(default = "default_weight")]
     pub weight: u64,
+
+    /// The options of the choice.
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the markov model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `weight` - How much chance this choice has of being chosen when there is no last choice, or
+///   when the last choice doesn't list a transition weight for it.
+/// * `transitions` - A mapping of choice names to how much chance those choices have of being
+///   chosen right after this choice.
+/// * `options` - The options of the choice.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct MarkovChoice {
+    pub name: String,
+    #[serde(default = "default_weight")]
+    pub weight: u64,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub transitions: BTreeMap<String, u64>,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the pairs model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `last_paired` - A mapping of the names of the choices that this choice has been paired with
+///   to the last round they were paired in. Rounds are counted from 1.
+/// * `sat_out` - How many times the choice has sat out because there was an odd number of choices.
+/// * `options` - The options of the choice.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PairChoice {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub last_paired: BTreeMap<String, u64>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub sat_out: u64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the quota model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `target` - The choice's share of the picks, relative to the other choices.
+/// * `picks` - How many times the choice has been accepted.
+/// * `options` - The options of the choice.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct QuotaChoice {
+    pub name: String,
+    #[serde(default = "default_weight")]
+    pub target: u64,
+    #[serde(default)]
+    pub picks: u64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the rated model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `rating` - The choice's Elo rating. Its chance of being chosen is proportional to this.
+/// * `comparisons` - How many times the choice has been compared to another choice.
+/// * `options` - The options of the choice.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct RatedChoice {
+    pub name: String,
+    #[serde(default = "default_rating")]
+    pub rating: f64,
+    #[serde(default)]
+    pub comparisons: u64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the recency model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `last_picked` - When the choice was last accepted, or `None` if it never has been.
+/// * `options` - The options of the choice.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecencyChoice {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_picked: Option<DateTime<FixedOffset>>,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Defines how likely each number in the range of a float range or integer range category is.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "shape")]
+pub enum RangeDistribution {
+    /// Every number in the range is equally likely.
+    #[default]
+    Uniform,
+    /// The numbers follow a normal distribution, cut off at the ends of the range. The `mean`
+    /// defaults to the middle of the range, and the `stddev` defaults to a sixth of the range.
+    Normal {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        mean: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        stddev: Option<f64>,
+    },
+}
+
+/// Defines how the weight of a choice in the recency model grows with the number of days since
+/// it was last picked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "shape")]
+pub enum RecencyCurve {
+    /// The weight is one plus the number of days since the choice was last picked.
+    #[default]
+    Linear,
+    /// The weight doubles every `doubling_days` days since the choice was last picked.
+    Exponential {
+        #[serde(default = "default_doubling_days")]
+        doubling_days: f64,
+    },
+    /// The weight grows like the linear curve, but stops growing after `max_days` days.
+    Capped { max_days: f64 },
+}
+
+/// Represents an individual participant for the secret santa model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the participant.
+/// * `exclude` - The names of the participants that this participant must not give a gift to,
+///   such as their spouse.
+/// * `options` - The options of the choice.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SecretSantaChoice {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents an individual choice for the spaced repetition model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `repetitions` - How many times in a row the choice has been recalled correctly.
+/// * `interval` - How many days the choice was last scheduled ahead.
+/// * `ease` - How quickly the interval grows. It is never less than 1.3.
+/// * `due` - When the choice is next due for review, or `None` if it has never been reviewed.
+/// * `options` - The options of the choice.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpacedRepetitionChoice {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub repetitions: u64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub interval: u64,
+    #[serde(default = "default_ease")]
+    pub ease: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due: Option<DateTime<FixedOffset>>,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Represents a named group of a stratified category.
+///
+/// # Attributes
+///
+/// * `name` - The name of the group.
+/// * `category` - The category that the group picks from. Its keys are written alongside the
+///   group's `name`.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct StratifiedGroup {
+    pub name: String,
+    #[serde(flatten)]
+    pub category: ConfigCategory,
+}
+
+/// Represents an individual choice for the teams model.
+///
+/// # Attributes
+///
+/// * `name` - The name of the choice.
+/// * `skill` - How much the choice adds to its team's total skill.
+/// * `options` - The options of the choice.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct TeamChoice {
+    pub name: String,
+    #[serde(default = "default_skill", skip_serializing_if = "is_default_skill")]
+    pub skill: f64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
 }
 
 /// Represents an individual choice for the weighted model.
@@ -168,11 +1199,91 @@ pub struct LotteryChoice {
 ///
 /// * `name` - The name of the choice
 /// * `weight` - How much chance this choice has of being chosen, relative to the other choices.
+/// * `options` - The options of the choice.
 #[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct WeightedChoice {
     pub name: String,
     #[serde(default = "default_weight")]
     pub weight: u64,
+    #[serde(flatten)]
+    pub options: ChoiceOptions,
+}
+
+/// Implement [`ConfigChoice`] for choice types that have `name` and `options` fields.
+macro_rules! impl_config_choice {
+    ($($choice:ty),*) => {
+        $(
+            impl ConfigChoice for $choice {
+                fn name(&self) -> &str {
+                    &self.name
+                }
+
+                fn options(&self) -> &ChoiceOptions {
+                    &self.options
+                }
+
+                fn options_mut(&mut self) -> &mut ChoiceOptions {
+                    &mut self.options
+                }
+            }
+        )*
+    };
+}
+
+impl_config_choice!(
+    BanditChoice,
+    BundleChoice,
+    Choice,
+    CompositeChoice,
+    DeckChoice,
+    InventoryChoice,
+    LfuChoice,
+    LootChoice,
+    LotteryChoice,
+    MarkovChoice,
+    PairChoice,
+    QuotaChoice,
+    RatedChoice,
+    RecencyChoice,
+    SecretSantaChoice,
+    SpacedRepetitionChoice,
+    TeamChoice,
+    WeightedChoice
+);
+
+/// Avoid repeating the most recent round by default.
+fn default_avoid_rounds() -> usize {
+    1
+}
+
+/// Define the default for the decay setting as 0.5.
+fn default_decay() -> f64 {
+    0.5
+}
+
+/// Define the default for the ease setting as 2.5, which is where SM-2 starts every item.
+fn default_ease() -> f64 {
+    2.5
+}
+
+/// Define the default for the exponent setting as 1.0.
+fn default_exponent() -> f64 {
+    1.0
+}
+
+/// Define the default for the doubling_days setting as 7.0.
+fn default_doubling_days() -> f64 {
+    7.0
+}
+
+/// Define the default for the skill setting as 1.0.
+fn default_skill() -> f64 {
+    1.0
+}
+
+/// Step through integer ranges one number at a time by default.
+fn default_step() -> u64 {
+    1
 }
 
 /// Define the default for the stddev_scaling_factor setting as 3.0.
@@ -180,11 +1291,37 @@ fn default_stddev_scaling_factor() -> f64 {
     3.0
 }
 
+/// Define the default for the p setting as 0.5.
+fn default_p() -> f64 {
+    0.5
+}
+
+/// Define the default for the rating setting as 1500.0, which is the customary starting rating in
+/// the Elo system.
+fn default_rating() -> f64 {
+    1500.0
+}
+
+/// Roll on a loot table once by default.
+fn default_rolls() -> u64 {
+    1
+}
+
 /// Reset to 0 by default.
 fn default_reset() -> u64 {
     0
 }
 
+/// Skip serializing counts that are 0, since that is their default.
+fn is_zero(count: &u64) -> bool {
+    *count == 0
+}
+
+/// Skip serializing skills that are the default skill.
+fn is_default_skill(skill: &f64) -> bool {
+    *skill == default_skill()
+}
+
 /// Define the default for the weight setting as 1.
 fn default_weight() -> u64 {
     1
@@ -196,8 +1333,109 @@ mod tests {
 
     #[test]
     fn test_defaults() {
+        assert_eq!(default_avoid_rounds(), 1);
+        assert!((default_decay() - 0.5).abs() < 0.000_001);
+        assert!((default_doubling_days() - 7.0).abs() < 0.000_001);
+        assert!((default_ease() - 2.5).abs() < 0.000_001);
+        assert!((default_exponent() - 1.0).abs() < 0.000_001);
+        assert!((default_p() - 0.5).abs() < 0.000_001);
+        assert!((default_rating() - 1500.0).abs() < 0.000_001);
+        assert!((default_skill() - 1.0).abs() < 0.000_001);
         assert!((default_stddev_scaling_factor() - 3.0).abs() < 0.000_001);
         assert_eq!(default_weight(), 1);
         assert_eq!(default_reset(), 0);
+        assert_eq!(default_rolls(), 1);
+        assert_eq!(default_step(), 1);
+    }
+
+    #[test]
+    fn test_convert_to_rated() {
+        let mut category: ConfigCategory = serde_yaml::from_str(
+            "
+model: weighted
+choices:
+  - name: this
+    weight: 1
+  - name: that
+    weight: 3
+    tags: [loud]
+",
+        )
+        .unwrap();
+
+        assert!(category.convert_to_rated());
+
+        let expected: ConfigCategory = serde_yaml::from_str(
+            "
+model: rated
+choices:
+  - name: this
+    rating: 750.0
+  - name: that
+    rating: 2250.0
+    tags: [loud]
+",
+        )
+        .unwrap();
+        assert_eq!(category, expected);
+        // Converting a rated category again changes nothing.
+        assert!(category.convert_to_rated());
+        assert_eq!(category, expected);
+    }
+
+    #[test]
+    fn test_convert_to_rated_other_model() {
+        let mut category = ConfigCategory::Even {
+            choices: vec!["this".into()],
+            cooldown: None,
+        };
+
+        assert!(!category.convert_to_rated());
+    }
+
+    #[test]
+    fn test_team_choice_default_skill_not_serialized() {
+        let choices: Vec<TeamChoice> = serde_yaml::from_str(
+            "
+- name: Alice
+- name: Bob
+  skill: 2
+",
+        )
+        .unwrap();
+
+        let serialized = serde_yaml::to_string(&choices).unwrap();
+
+        assert_eq!(serialized, "- name: Alice\n- name: Bob\n  skill: 2.0\n");
+    }
+
+    #[test]
+    fn test_schedule_includes() {
+        let schedule: Schedule = serde_yaml::from_str(
+            "
+days: [fri, sat]
+times:
+  - start: '22:00'
+    end: '02:00'
+dates:
+  - start: 2026-06-01
+    end: 2026-08-31
+",
+        )
+        .unwrap();
+        let at = |s: &str| NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap();
+
+        // Friday night, and early Saturday morning.
+        assert!(schedule.includes(at("2026-06-05 23:00")));
+        assert!(schedule.includes(at("2026-06-06 01:59")));
+        // The end of the time range is not included.
+        assert!(!schedule.includes(at("2026-06-06 02:00")));
+        // Thursday is not one of the days.
+        assert!(!schedule.includes(at("2026-06-04 23:00")));
+        // Both ends of the date range are included.
+        assert!(schedule.includes(at("2026-08-29 22:00")));
+        assert!(!schedule.includes(at("2026-09-04 22:00")));
+        // An empty schedule is always available.
+        assert!(Schedule::default().includes(at("2026-09-04 12:00")));
     }
 }
@@ -13,16 +13,95 @@ along with this program.  If not, see <http://www.gnu.org/licenses/>.*/
 //! # The Engine
 //!
 //! This module defines the Engine, the core of the rpick crate.
-use std::collections::BTreeMap;
+use std::{cmp::Ordering, collections::BTreeMap, iter::Sum};
 
-use rand::Rng;
-use rand::seq::IndexedRandom;
-use rand_distr::{Distribution, Normal};
+use chrono::{DateTime, FixedOffset};
+use rand::distr::uniform::SampleUniform;
+use rand::distr::weighted::WeightedIndex;
+use rand::seq::{IndexedRandom, SliceRandom};
+use rand::{Rng, RngExt};
+use rand_distr::{Beta, Distribution};
 use statrs::distribution::ContinuousCDF;
 use thiserror::Error;
 
 use crate::{config, ui};
 
+/// The number of seconds in a day, which is used to express durations in days.
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// How many rounds of Thompson sampling the bandit model uses to find its weights.
+const THOMPSON_ROUNDS: u64 = 1_000;
+
+/// How far a single comparison can move a rating in a tournament.
+const ELO_K_FACTOR: f64 = 32.0;
+
+/// How many rounds of bisection are used to find the gaussian model's standard deviation from the
+/// chance of its last item.
+const GAUSSIAN_SEARCH_ROUNDS: u64 = 100;
+
+/// How many times the teams model tries to deal the choices out to teams at random before it gives
+/// up on satisfying the constraints.
+const TEAM_ATTEMPTS: usize = 100;
+
+/// The most dice that a dice category can roll at once.
+const MAX_DICE: u64 = 100;
+
+/// The most sides that a die in a dice category can have.
+const MAX_SIDES: u64 = 1_000;
+
+/// The most times that a loot category can roll on its table, and the most times that one of its
+/// choices can reroll its table.
+const MAX_ROLLS: u64 = 1_000;
+
+/// The most rows that the verbose tables of the dice and range models show. Longer distributions
+/// are shown in groups of neighbouring numbers.
+const DISTRIBUTION_ROWS: usize = 20;
+
+/// How far the costs of a bundle may add up to beyond its budget, so that rounding errors in the
+/// sum of the costs don't keep choices out of the bundle.
+const BUDGET_TOLERANCE: f64 = 1e-9;
+
+/// The lowest ease that the spaced repetition model lets a choice fall to, as in SM-2.
+const MIN_EASE: f64 = 1.3;
+
+/// A list of candidates for [`Engine::pick_weighted_common`], as described in its documentation.
+type WeightedCandidates<'a, W> = Vec<((usize, &'a String), W)>;
+
+/// An extra column that a model can add to the weighted chance table.
+///
+/// The first element is the column's header. The second element is given the original index of a
+/// choice, and should return the cell to display for that choice.
+type Column<'f> = (&'static str, &'f dyn Fn(usize) -> ui::Cell<'f>);
+
+/// A type that can be used as a weight in [`Engine::pick_weighted_common`].
+trait Weight: Copy + PartialOrd + SampleUniform + Sum + rand::distr::weighted::Weight {
+    /// Express the weight as a float, so that chances can be computed from it.
+    fn to_f64(self) -> f64;
+
+    /// Express the weight as a cell for the weighted chance table.
+    fn to_cell(self) -> ui::Cell<'static>;
+}
+
+impl Weight for u64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn to_cell(self) -> ui::Cell<'static> {
+        ui::Cell::Unsigned(self)
+    }
+}
+
+impl Weight for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn to_cell(self) -> ui::Cell<'static> {
+        ui::Cell::Decimal(self)
+    }
+}
+
 /// The rpick Engine object allows you to write your own rpick interface.
 ///
 /// # Attributes
@@ -30,9 +109,18 @@ use crate::{config, ui};
 /// * `ui` - This is a struct that implements the [`ui::Ui`] trait.
 /// * `rng` - This must be a random number generator that implements the [`rand::RngCore`]
 ///   trait.
+/// * `now` - The time that the engine considers to be the current time.
+/// * `tags` - Only choices that have all of these tags are offered.
+/// * `not_tags` - Choices that have any of these tags are not offered.
+/// * `excluded` - The names of the choices in the current category that can't be offered right
+///   now, along with the reason why. These are listed at the bottom of the chance tables.
 pub struct Engine<'ui, U> {
     ui: &'ui U,
     rng: Box<dyn rand::Rng>,
+    now: DateTime<FixedOffset>,
+    tags: Vec<String>,
+    not_tags: Vec<String>,
+    excluded: Vec<(String, String)>,
 }
 
 impl<'a, 'ui, U> Engine<'ui, U>
@@ -51,11 +139,18 @@ where
         Engine {
             ui,
             rng: Box::new(rng),
+            now: chrono::Local::now().fixed_offset(),
+            tags: vec![],
+            not_tags: vec![],
+            excluded: vec![],
         }
     }
 
     /// Pick an item from the [`config::ConfigCategory`] referenced by the given `category`.
     ///
+    /// If the chosen item refers to another category, an item is then picked from that category
+    /// too, and so on.
+    ///
     /// # Arguments
     ///
     /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects, which
@@ -64,637 +159,8080 @@ where
     ///
     /// # Returns
     ///
-    /// This will return the chosen item.
+    /// This will return the chosen item. If the item referred to another category, this will be
+    /// the full path of the pick, such as `restaurant → Sitti`.
     pub fn pick<'c>(
         &mut self,
-        config: &'c mut BTreeMap<String, config::ConfigCategory>,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
         category: &'c str,
-    ) -> Result<&'c str, PickError<'c>> {
-        let config_category = config.get_mut(category);
-        match config_category {
-            Some(category) => match category {
-                config::ConfigCategory::Even { choices } => Ok(self.pick_even(choices)),
-                config::ConfigCategory::Gaussian {
-                    choices,
-                    stddev_scaling_factor,
-                } => Ok(self.pick_gaussian(choices, *stddev_scaling_factor)),
-                config::ConfigCategory::Inventory { choices } => Ok(self.pick_inventory(choices)),
-                config::ConfigCategory::Lottery { choices } => Ok(self.pick_lottery(choices)),
-                config::ConfigCategory::Lru { choices } => Ok(self.pick_lru(choices)),
-                config::ConfigCategory::Weighted { choices } => Ok(self.pick_weighted(choices)),
-            },
-            None => Err(PickError::CategoryNotFound(category)),
-        }
+    ) -> Result<String, PickError<'c>> {
+        // pick_many() always returns exactly as many items as we ask for, so this can't fail.
+        Ok(self.pick_many(config, category, 1)?.remove(0))
     }
 
-    /// Use the given random number generator rather than the default.
-    pub fn set_rng<R: 'static + Rng>(&mut self, rng: R) {
-        self.rng = Box::new(rng);
-    }
-
-    /// Express disapproval to the user.
-    fn express_disapproval(&mut self) {
-        self.ui.info("🤨");
-    }
-
-    /// Prompt the user for consent for the given choice, returning a bool true if they accept the
-    /// choice, or false if they do not.
-    fn get_consent(&mut self, choice: &str) -> bool {
-        self.ui.prompt_choice(choice)
-    }
-
-    /// Use an even distribution random model to pick from the given choices.
-    fn pick_even<'c>(&mut self, choices: &'c [String]) -> &'c str {
-        let initialize_candidates = || {
-            choices
-                .iter()
-                .enumerate()
-                .map(|x| ((x.0, x.1), 1))
-                .collect::<Vec<_>>()
-        };
-
-        let index = self.pick_weighted_common(&initialize_candidates);
+    /// Pick `count` distinct items from the [`config::ConfigCategory`] referenced by the given
+    /// `category`.
+    ///
+    /// Each item is offered to the user in turn, and items that have already been accepted are
+    /// not offered again. Once all the items have been accepted, the category's state is updated
+    /// for each of them in the order that they were picked. Any of the items that refer to
+    /// another category then go on to pick an item from that category.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects, which
+    ///   contain the parameters which should be used for the pick.
+    /// * `category` - The category you wish to choose from.
+    /// * `count` - How many distinct items to pick.
+    ///
+    /// # Returns
+    ///
+    /// This will return the chosen items, in the order they were picked. Items that referred to
+    /// other categories are expressed as paths, as described in [`Engine::pick`].
+    pub fn pick_many<'c>(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+        count: usize,
+    ) -> Result<Vec<String>, PickError<'c>> {
+        if !config.contains_key(category) {
+            return Err(PickError::CategoryNotFound(category));
+        }
+        // Let's make sure all the categories we might end up in exist and don't form a cycle
+        // before we bother the user with any questions.
+        check_references(config, &mut vec![category.to_string()])?;
 
-        &choices[index]
+        self.pick_nested(config, category, count, true)
     }
 
-    /// Run the gaussian model for the given choices and standard deviation scaling factor. When the
-    /// user accepts a choice, move that choice to end of the choices Vector and return.
-    fn pick_gaussian<'c>(
+    /// Run a tournament on the [`config::ConfigCategory`] referenced by the given `category`,
+    /// asking the user to compare `rounds` pairs of choices and updating the choices' Elo ratings
+    /// with the results.
+    ///
+    /// Each pair is made of a choice that has been compared the fewest times, and a random other
+    /// choice. Weighted categories are turned into rated categories first, as described in
+    /// [`config::ConfigCategory::convert_to_rated`].
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects.
+    /// * `category` - The category you wish to rate. It must be a weighted or rated category.
+    /// * `rounds` - How many pairs of choices to compare.
+    pub fn run_tournament<'c>(
         &mut self,
-        choices: &'c mut Vec<String>,
-        stddev_scaling_factor: f64,
-    ) -> &'c str {
-        let mut candidates = choices.clone();
-        let mut index;
-
-        loop {
-            let stddev = (candidates.len() as f64) / stddev_scaling_factor;
-            let normal = Normal::new(0.0, stddev).unwrap();
-            index = normal.sample(&mut self.rng).abs() as usize;
-
-            if let Some(value) = candidates.get(index) {
-                if self.ui.call_display_table() {
-                    self.display_gaussian_chance_table(index, &candidates, stddev);
-                }
-
-                if self.get_consent(&value[..]) {
-                    index = choices.iter().position(|x| x == value).unwrap();
-                    break;
-                } else if candidates.len() > 1 {
-                    index = candidates.iter().position(|x| x == value).unwrap();
-                    candidates.remove(index);
-                } else {
-                    self.express_disapproval();
-                    candidates = choices.clone();
-                }
-            }
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+        rounds: usize,
+    ) -> Result<(), PickError<'c>> {
+        let config_category = config
+            .get_mut(category)
+            .ok_or(PickError::CategoryNotFound(category))?;
+        if !config_category.convert_to_rated() {
+            return Err(PickError::CannotRate(category));
+        }
+        let config::ConfigCategory::Rated { choices, .. } = config_category else {
+            unreachable!("convert_to_rated() returned true for a category that isn't rated");
+        };
+        if rounds > 0 && choices.len() < 2 {
+            return Err(PickError::NotEnoughChoices {
+                category: category.to_string(),
+                requested: 2,
+                available: choices.len(),
+            });
         }
 
-        let value = choices.remove(index);
-        choices.push(value);
-
-        &choices[choices.len() - 1]
-    }
-
-    /// Run the inventory model for the given choices.
-    fn pick_inventory<'c>(&mut self, choices: &'c mut [config::InventoryChoice]) -> &'c str {
-        let initialize_candidates = || {
-            choices
-                .iter()
-                .enumerate()
-                .filter(|x| x.1.tickets > 0)
-                .map(|x| ((x.0, &x.1.name), x.1.tickets))
+        for _ in 0..rounds {
+            let fewest = choices.iter().map(|c| c.comparisons).min().unwrap();
+            let first = *(0..choices.len())
+                .filter(|i| choices[*i].comparisons == fewest)
                 .collect::<Vec<_>>()
-        };
+                .choose(&mut self.rng)
+                .unwrap();
+            let second = *(0..choices.len())
+                .filter(|i| *i != first)
+                .collect::<Vec<_>>()
+                .choose(&mut self.rng)
+                .unwrap();
 
-        let index = self.pick_weighted_common(&initialize_candidates);
+            let first_won = self
+                .ui
+                .prompt_comparison(&choices[first].name, &choices[second].name);
 
-        choices[index].tickets -= 1;
+            let (winner, loser) = if first_won {
+                (first, second)
+            } else {
+                (second, first)
+            };
+            let change = elo_change(choices[winner].rating, choices[loser].rating);
+            choices[winner].rating += change;
+            choices[loser].rating -= change;
+            choices[first].comparisons += 1;
+            choices[second].comparisons += 1;
+        }
 
-        &choices[index].name
-    }
+        if self.ui.call_display_table() {
+            self.display_ratings_table(choices);
+        }
 
-    /// Run the Lru model for the given choices. When the user accepts a choice, move that choice to
-    /// the end of the choices Vector and return.
-    fn pick_lru<'c>(&mut self, choices: &'c mut Vec<String>) -> &'c str {
-        for (index, choice) in choices.iter().enumerate() {
-            if self.ui.call_display_table() {
-                self.display_lru_table(index, choices);
-            }
+        Ok(())
+    }
 
-            if self.get_consent(&choice[..]) {
-                let chosen = choices.remove(index);
-                choices.push(chosen);
+    /// Put the choices of the [`config::ConfigCategory`] referenced by the given `category` in a
+    /// random order that follows the chances of the category's model, rather than picking one of
+    /// them.
+    ///
+    /// Weighted, lottery, and inventory categories are ordered by picking each position in turn
+    /// from the choices that are left, with their weights or tickets. Gaussian categories do the
+    /// same with the chances of their positions, so choices near the beginning of the list tend
+    /// to stay near the beginning. Even categories are shuffled, and lru categories keep the order
+    /// of their list. Choices that have no chance of being picked go at the end, in the order of
+    /// the list. Only the choices that match the engine's tag filter and are available right now
+    /// are ordered.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects.
+    /// * `category` - The category you wish to order.
+    /// * `update` - If true, the category is updated as if each choice had been accepted, in
+    ///   order.
+    ///
+    /// # Returns
+    ///
+    /// The names of the choices, in order.
+    pub fn order<'c>(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+        update: bool,
+    ) -> Result<Vec<String>, PickError<'c>> {
+        let config_category = config
+            .get_mut(category)
+            .ok_or(PickError::CategoryNotFound(category))?;
+        check_parameters(category, config_category)?;
+        let (matched, eligible) = self.eligibility(config_category, true);
+        let order = self
+            .order_choices(config_category, &eligible)
+            .ok_or(PickError::CannotOrder(category))?;
+        let choices = category_choices(config_category);
+        let names = order
+            .iter()
+            .map(|i| choices[*i].name().to_string())
+            .collect();
 
-                // We can be sure there is a last since we just pushed it.
-                return choices.last().unwrap();
-            }
+        if update {
+            let picked = update_ordered(config_category, &eligible, &order);
+            self.update_cooldowns(config_category, &matched, &picked, picked.len() as u64);
         }
 
-        self.express_disapproval();
-
-        // If we've gotten here, the user hasn't made a choice. So… let's do it again!
-        self.pick_lru(choices)
+        Ok(names)
     }
 
-    /// Run the lottery model for the given choices.
-    fn pick_lottery<'c>(&mut self, choices: &'c mut [config::LotteryChoice]) -> &'c str {
-        let initialize_candidates = || {
-            choices
-                .iter()
-                .enumerate()
-                .filter(|x| x.1.tickets > 0)
-                .map(|x| ((x.0, &x.1.name), x.1.tickets))
-                .collect::<Vec<_>>()
+    /// Roll on the loot [`config::ConfigCategory`] referenced by the given `category`.
+    ///
+    /// Only the choices that match the engine's tag filter and are available right now can be
+    /// rolled. Each roll is offered to the user, and is rolled again if they reject it.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects.
+    /// * `category` - The category you wish to roll on. It must be a loot category.
+    ///
+    /// # Returns
+    ///
+    /// The names of the dropped items, starting with the guaranteed items and followed by the
+    /// rolled items in the order that they were rolled.
+    pub fn roll_loot<'c>(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+    ) -> Result<Vec<String>, PickError<'c>> {
+        let config_category = config
+            .get_mut(category)
+            .ok_or(PickError::CategoryNotFound(category))?;
+        check_outcome_choices(category, config_category)?;
+        let (matched, eligible) = self.eligibility(config_category, true);
+        let config::ConfigCategory::Loot {
+            rolls,
+            guaranteed,
+            choices,
+            ..
+        } = config_category
+        else {
+            return Err(PickError::WrongModel {
+                category,
+                model: "loot",
+            });
         };
 
-        let index = self.pick_weighted_common(&initialize_candidates);
-
-        for choice in choices.iter_mut() {
-            choice.tickets += choice.weight;
-        }
-        choices[index].tickets = choices[index].reset;
+        let (drops, rolled) = self.pick_loot(category, choices, &eligible, *rolls, guaranteed)?;
+        let rolled = choice_indices(config_category, &rolled);
+        self.update_cooldowns(config_category, &matched, &rolled, 1);
 
-        &choices[index].name
+        Ok(drops)
     }
 
-    /// Run the weighted model for the given choices.
-    fn pick_weighted<'c>(&mut self, choices: &'c [config::WeightedChoice]) -> &'c str {
-        let initialize_candidates = || {
-            choices
-                .iter()
-                .enumerate()
-                .map(|x| ((x.0, &x.1.name), x.1.weight))
-                .collect::<Vec<_>>()
+    /// Split the choices of the pairs [`config::ConfigCategory`] referenced by the given
+    /// `category` into pairs.
+    ///
+    /// Only the choices that match the engine's tag filter and are available right now are
+    /// paired. The pairs are offered to the user, and new pairs are drawn if they reject them.
+    /// Once the user accepts the pairs, they are recorded in the category's history.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects.
+    /// * `category` - The category you wish to pair up. It must be a pairs category.
+    ///
+    /// # Returns
+    ///
+    /// The accepted [`Pairs`].
+    pub fn pair_up<'c>(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+    ) -> Result<Pairs, PickError<'c>> {
+        let config_category = config
+            .get_mut(category)
+            .ok_or(PickError::CategoryNotFound(category))?;
+        check_outcome_choices(category, config_category)?;
+        let (matched, eligible) = self.eligibility(config_category, true);
+        let config::ConfigCategory::Pairs {
+            rounds, choices, ..
+        } = config_category
+        else {
+            return Err(PickError::WrongModel {
+                category,
+                model: "pairs",
+            });
         };
 
-        let index = self.pick_weighted_common(&initialize_candidates);
+        let pairs = self.pick_pairs(category, choices, &eligible, rounds)?;
+        let paired = pairs
+            .pairs
+            .iter()
+            .flat_map(|(a, b)| [a.clone(), b.clone()])
+            .collect::<Vec<_>>();
+        let paired = choice_indices(config_category, &paired);
+        self.update_cooldowns(config_category, &matched, &paired, 1);
 
-        &choices[index].name
+        Ok(pairs)
     }
 
-    /// A common weighted choice algorithm used as the core of many models.
+    /// Assign each participant of the secret santa [`config::ConfigCategory`] referenced by the
+    /// given `category` another participant to give a gift to.
     ///
-    /// The initialize_candidates() function should return a Vector of 2-tuples. The first element
-    /// of the 2-tuple is also a 2-tuple, specifying the original index of the choice and the human
-    /// readable name of the choice. The second element of the outer 2-tuple should express the
-    /// weight of that choice. For example, if the first choice is "ice cream" and has a weight of
-    /// 5, the data structure would look like this: ((0, "ice cream"), 5)
-    fn pick_weighted_common(
+    /// Only the participants that match the engine's tag filter and are available right now take
+    /// part. The assignment is offered to the user, and a new assignment is drawn if they reject
+    /// it. Once the user accepts an assignment, it is added to the category's history.
+    ///
+    /// # Arguments
+    ///
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects.
+    /// * `category` - The category you wish to assign. It must be a secret santa category.
+    ///
+    /// # Returns
+    ///
+    /// The accepted assignment, as pairs of givers and their recipients, in the order that the
+    /// participants are listed in the category.
+    pub fn assign_secret_santa<'c>(
         &mut self,
-        initialize_candidates: &dyn Fn() -> Vec<((usize, &'a String), u64)>,
-    ) -> usize {
-        let mut candidates = initialize_candidates();
-
-        loop {
-            let (index, choice) = candidates
-                .choose_weighted(&mut self.rng, |item| item.1)
-                .unwrap()
-                .0;
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+    ) -> Result<Vec<(String, String)>, PickError<'c>> {
+        let config_category = config
+            .get_mut(category)
+            .ok_or(PickError::CategoryNotFound(category))?;
+        check_outcome_choices(category, config_category)?;
+        let (matched, eligible) = self.eligibility(config_category, true);
+        let config::ConfigCategory::SecretSanta {
+            avoid_rounds,
+            history,
+            choices,
+            ..
+        } = config_category
+        else {
+            return Err(PickError::WrongModel {
+                category,
+                model: "secret_santa",
+            });
+        };
 
-            if self.ui.call_display_table() {
-                self.display_weighted_chance_table(index, &candidates);
-            }
+        let assignment =
+            self.pick_secret_santa(category, choices, &eligible, *avoid_rounds, history)?;
+        let givers = assignment
+            .iter()
+            .map(|(giver, _)| giver.clone())
+            .collect::<Vec<_>>();
+        let givers = choice_indices(config_category, &givers);
+        self.update_cooldowns(config_category, &matched, &givers, 1);
 
-            if self.get_consent(&choice[..]) {
-                break index;
-            } else if candidates.len() > 1 {
-                candidates.remove(candidates.iter().position(|x| (x.0).1 == choice).unwrap());
-            } else {
-                self.express_disapproval();
-                candidates = initialize_candidates();
-            }
-        }
+        Ok(assignment)
     }
 
-    /// Print a table to self.output showing the candidates, sorted by chance of being chosen.
+    /// Split the choices of the teams [`config::ConfigCategory`] referenced by the given
+    /// `category` into teams.
+    ///
+    /// Only the choices that match the engine's tag filter and are available right now are put on
+    /// teams. The teams are offered to the user, and new teams are drawn if they reject them.
     ///
     /// # Arguments
     ///
-    /// `index` - The index of the candidate that was chosen. This is used to turn the chosen
-    ///     candidate yellow in the table.
-    /// `candidates` - A list of the candidates.
-    fn display_gaussian_chance_table(&mut self, index: usize, candidates: &[String], stddev: f64) {
-        // Let's make a copy of the candidate list so that we can sort it for the table
-        // without sorting the real candidate list.
-        let candidates = candidates.to_owned();
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects.
+    /// * `category` - The category you wish to split. It must be a teams category.
+    ///
+    /// # Returns
+    ///
+    /// The accepted teams, each listing the names of its members in the order that they are
+    /// listed in the category.
+    pub fn split_teams<'c>(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+    ) -> Result<Vec<Vec<String>>, PickError<'c>> {
+        let config_category = config
+            .get_mut(category)
+            .ok_or(PickError::CategoryNotFound(category))?;
+        check_outcome_choices(category, config_category)?;
+        let (matched, eligible) = self.eligibility(config_category, true);
+        let config::ConfigCategory::Teams {
+            teams,
+            team_size,
+            together,
+            apart,
+            choices,
+            ..
+        } = config_category
+        else {
+            return Err(PickError::WrongModel {
+                category,
+                model: "teams",
+            });
+        };
 
-        let header: Vec<ui::Cell> = vec!["Name".into(), "Chance".into()];
-        let mut rows = vec![];
-        let distribution = statrs::distribution::Normal::new(0.0, stddev).unwrap();
-        let mut total_chance = 0.0;
-        for (i, candidate) in candidates.iter().enumerate() {
-            // We multiply by 200 here: 100 is for expressing percents to humans, and the factor
-            // of 2 is to account for the abs() we use in pick_gaussian(), which causes us to
-            // reflect the distribution around the x-axis (thus the chance is this slice of the CDF
-            // on both sides of the x-axis, which is the same chance as twice this singular slice).
-            let chance: f64 =
-                (distribution.cdf((i as f64) + 1.0) - distribution.cdf(i as f64)) * 200.;
-            total_chance += chance;
-            let mut cells: Vec<ui::Cell> = vec![];
-            let chosen = i == index;
-            cells.push(ui::Cell::from(candidate.as_ref()));
-            cells.push(chance.into());
-            let row = ui::Row { cells, chosen };
-            rows.push(row);
-        }
-        let footer: Vec<ui::Cell> = vec!["Total".into(), total_chance.into()];
+        let team_count = team_count(&eligible, *teams, *team_size);
+        let teams = self.pick_teams(category, choices, &eligible, team_count, together, apart)?;
+        let players = choice_indices(config_category, &teams.concat());
+        self.update_cooldowns(config_category, &matched, &players, 1);
 
-        self.ui.display_table(&ui::Table {
-            footer,
-            header,
-            rows,
-        });
+        Ok(teams)
     }
 
-    /// Print a table to self.output showing the candidates, sorted by chance of being chosen.
+    /// Pick a set of the choices of the bundle [`config::ConfigCategory`] referenced by the given
+    /// `category`, whose total cost stays within the category's budget.
+    ///
+    /// Only the choices that match the engine's tag filter and are available right now can be
+    /// added to the set. The set is offered to the user, and a new set is drawn if they reject it.
     ///
     /// # Arguments
     ///
-    /// `index` - The index of the candidate that was chosen. This is used to turn the chosen
-    ///     candidate yellow in the table.
-    /// `candidates` - A list of the candidates.
-    fn display_lru_table(&mut self, index: usize, candidates: &[String]) {
-        // Filter out candidates that have already been rejected by the user.
-        let candidates = candidates
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| i >= &index)
-            .map(|x| x.1)
-            .collect::<Vec<_>>();
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects.
+    /// * `category` - The category you wish to pick a set from. It must be a bundle category.
+    ///
+    /// # Returns
+    ///
+    /// The accepted [`Bundle`].
+    pub fn assemble_bundle<'c>(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+    ) -> Result<Bundle, PickError<'c>> {
+        let config_category = config
+            .get_mut(category)
+            .ok_or(PickError::CategoryNotFound(category))?;
+        check_outcome_choices(category, config_category)?;
+        let (matched, eligible) = self.eligibility(config_category, true);
+        let config::ConfigCategory::Bundle {
+            budget, choices, ..
+        } = config_category
+        else {
+            return Err(PickError::WrongModel {
+                category,
+                model: "bundle",
+            });
+        };
 
-        let header: Vec<ui::Cell> = vec!["Name".into()];
-        let mut rows = vec![];
-        for (i, candidate) in candidates.iter().rev().enumerate() {
-            let mut cells: Vec<ui::Cell> = vec![];
-            let chosen = i == candidates.len() - 1;
-            cells.push(ui::Cell::from(candidate.as_ref()));
-            rows.push(ui::Row { cells, chosen });
-        }
-        let footer = vec![];
+        let bundle = self.pick_bundle(category, choices, &eligible, *budget)?;
+        let picked = choice_indices(config_category, &bundle.choices);
+        self.update_cooldowns(config_category, &matched, &picked, 1);
 
-        self.ui.display_table(&ui::Table {
-            footer,
-            header,
-            rows,
-        });
+        Ok(bundle)
     }
 
-    /// Print a table to self.output showing the candidates, sorted by chance of being chosen.
+    /// Pick one item from each group of the stratified [`config::ConfigCategory`] referenced by the
+    /// given `category`, in the order that the groups are listed.
+    ///
+    /// Each group is picked from with its own model, so the user accepts or rejects the items of
+    /// each group in turn, and each group's state is updated on its own. Any of the items that
+    /// refer to another category then go on to pick an item from that category.
     ///
     /// # Arguments
     ///
-    /// `index` - The index of the candidate that was chosen. This is used to turn the chosen
-    ///     candidate yellow in the table.
-    /// `candidates` - A list of the candidates.
-    fn display_weighted_chance_table(
-        &mut self,
-        index: usize,
-        candidates: &[((usize, &'a String), u64)],
-    ) {
-        // Let's make a copy of the candidate list so that we can sort it for the table
-        // without sorting the real candidate list.
-        let mut candidates = candidates.to_owned();
-        candidates.sort_by_key(|c| c.1);
+    /// * `config` - A mapping of category names to [`config::ConfigCategory`] objects.
+    /// * `category` - The category you wish to pick from. It must be a stratified category.
+    ///
+    /// # Returns
+    ///
+    /// The name of each group, along with the item that was picked from it. Items that referred
+    /// to other categories are expressed as paths, as described in [`Engine::pick`].
+    pub fn pick_stratified<'c>(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &'c str,
+    ) -> Result<Vec<(String, String)>, PickError<'c>> {
+        match config.get(category) {
+            Some(config::ConfigCategory::Stratified { .. }) => {}
+            Some(_) => {
+                return Err(PickError::WrongModel {
+                    category,
+                    model: "stratified",
+                });
+            }
+            None => return Err(PickError::CategoryNotFound(category)),
+        }
+        check_references(config, &mut vec![category.to_string()])?;
 
-        let total: u64 = candidates.iter().map(|x| x.1).sum();
+        let mut config_category = config.remove(category).unwrap();
+        let config::ConfigCategory::Stratified { groups } = &mut config_category else {
+            unreachable!("the category was checked to be stratified above");
+        };
+        let picks = self.pick_groups(config, category, groups, true);
+        config.insert(category.to_string(), config_category);
 
-        let mut rows = vec![];
-        let header: Vec<ui::Cell> = vec!["Name".into(), "Weight".into(), "Chance".into()];
-        for candidate in candidates.iter() {
-            let chance: f64 = (candidate.1 as f64) / (total as f64) * 100.;
-            let mut cells: Vec<ui::Cell> = vec![];
-            let chosen = (candidate.0).0 == index;
-            cells.push(ui::Cell::from((candidate.0).1.as_ref()));
-            cells.push(candidate.1.into());
-            cells.push(chance.into());
-            rows.push(ui::Row { cells, chosen });
-        }
-        let footer: Vec<ui::Cell> = vec!["Total".into(), total.into(), 100.00.into()];
+        picks
+    }
 
-        self.ui.display_table(&ui::Table {
-            footer,
-            header,
-            rows,
-        });
+    /// Pick `count` distinct items from the given category, and then pick from any categories
+    /// that the chosen items refer to.
+    ///
+    /// The category and all the categories it refers to must have already been validated by
+    /// [`check_references`]. If `filtered` is true, only the choices that match the engine's tag
+    /// filter are offered from the given category. The categories that the chosen items refer to
+    /// are never filtered by tags. Choices that are unavailable right now are never offered.
+    fn pick_nested(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &str,
+        count: usize,
+        filtered: bool,
+    ) -> Result<Vec<String>, PickError<'static>> {
+        // The category is taken out of the config while we pick from it, so that the categories
+        // its items refer to can be picked from too. They can't refer back to it, since
+        // check_references() rules out cycles.
+        let mut config_category = config.remove(category).unwrap();
+        let picked =
+            self.pick_from_category(config, category, &mut config_category, count, filtered);
+        config.insert(category.to_string(), config_category);
+
+        picked
     }
-}
 
-/// Define the errors that can be returned from [`Engine::pick`].
-#[derive(Debug, Error)]
-pub enum PickError<'e> {
-    #[error("The category `{0}` was not found in the given config.")]
-    CategoryNotFound(&'e str),
-}
+    /// Pick `count` distinct items from the given category, which has been taken out of the
+    /// config, as described in [`Engine::pick_nested`]. The `category` name is used in errors.
+    fn pick_from_category(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &str,
+        config_category: &mut config::ConfigCategory,
+        count: usize,
+        filtered: bool,
+    ) -> Result<Vec<String>, PickError<'static>> {
+        if let config::ConfigCategory::Stratified { groups } = config_category {
+            if count > 1 {
+                return Err(PickError::NotEnoughChoices {
+                    category: category.to_string(),
+                    requested: count,
+                    available: 1,
+                });
+            }
+            let picks = self.pick_groups(config, category, groups, filtered)?;
+            return Ok(vec![format_groups(&picks)]);
+        }
 
-#[cfg(test)]
-mod tests {
-    use std::convert::Infallible;
+        let (matched, eligible) = self.eligibility(config_category, filtered);
+        if let Some(outcome) = self.pick_outcome(category, config_category, &eligible, count) {
+            let (outcome, accepted) = outcome?;
+            let accepted = choice_indices(config_category, &accepted);
+            self.update_cooldowns(config_category, &matched, &accepted, 1);
+            return Ok(vec![outcome]);
+        }
+        let available = available_choices(config_category, &eligible);
+        if count > available {
+            return Err(PickError::NotEnoughChoices {
+                category: category.to_string(),
+                requested: count,
+                available,
+            });
+        }
 
-    use approx::abs_diff_eq;
-    use mockall::{mock, predicate};
-    use rand::SeedableRng;
+        check_parameters(category, config_category)?;
+        let picked = self.pick_category(category, config_category, &eligible, count)?;
+        self.update_cooldowns(config_category, &matched, &picked, picked.len() as u64);
 
-    use super::*;
+        let choices = category_choices(config_category);
+        let picked = picked
+            .iter()
+            .map(|i| {
+                (
+                    choices[*i].name().to_string(),
+                    choices[*i].options().category.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
 
-    struct FakeRng(u32);
+        picked
+            .into_iter()
+            .map(|(name, nested)| match nested {
+                Some(nested) => {
+                    let path = format!(
+                        "{} → {}",
+                        name,
+                        self.pick_nested(config, &nested, 1, false)?.remove(0)
+                    );
+                    self.ui.info(&format!("Picked: {}", path));
+                    Ok(path)
+                }
+                None => Ok(name),
+            })
+            .collect()
+    }
 
-    /// This allows our tests to have predictable results, and to have the same predictable results
-    /// on both 32-bit and 64-bit architectures. This is used for all tests except for the Gaussian
-    /// tests, since those do behave differently between 32-bit and 64-bit systems when using this
-    /// rng.
-    impl rand::TryRng for FakeRng {
-        type Error = Infallible;
+    /// Pick one item from each of the given groups of a stratified category, in order, and return
+    /// the name of each group along with the item that was picked from it. Once every group has
+    /// been picked from, all of the picks are shown to the user.
+    fn pick_groups(
+        &mut self,
+        config: &mut BTreeMap<String, config::ConfigCategory>,
+        category: &str,
+        groups: &mut [config::StratifiedGroup],
+        filtered: bool,
+    ) -> Result<Vec<(String, String)>, PickError<'static>> {
+        let picks = groups
+            .iter_mut()
+            .map(|group| {
+                let path = format!("{} → {}", category, group.name);
+                let mut picked =
+                    self.pick_from_category(config, &path, &mut group.category, 1, filtered)?;
+                Ok((group.name.clone(), picked.remove(0)))
+            })
+            .collect::<Result<Vec<_>, PickError<'static>>>()?;
+        self.ui.info(&format!("Picked: {}", format_groups(&picks)));
 
-        fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
-            self.0 += 1;
+        Ok(picks)
+    }
 
-            Ok(self.0 - 1)
+    /// Return which of the given category's choices match the engine's tag filter, and which of
+    /// those are eligible to be picked right now. The tag filter is only applied if `filtered` is
+    /// true. The choices that match the tag filter but aren't eligible are recorded in
+    /// `self.excluded`, along with the reason they were excluded.
+    fn eligibility(
+        &mut self,
+        category: &config::ConfigCategory,
+        filtered: bool,
+    ) -> (Vec<bool>, Vec<bool>) {
+        let matched = category_choices(category)
+            .iter()
+            .map(|c| !filtered || self.matches_tags(c.options()))
+            .collect::<Vec<_>>();
+        self.excluded.clear();
+        let eligible = category_choices(category)
+            .iter()
+            .zip(matched.iter())
+            .map(|(c, matched)| {
+                if !matched {
+                    return false;
+                }
+                match self.exclusion_reason(c.options()) {
+                    Some(reason) => {
+                        self.excluded.push((c.name().to_string(), reason));
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        (matched, eligible)
+    }
+
+    /// If the given category's model produces a single outcome rather than picking some of its
+    /// choices, such as an arrangement of all of its eligible choices, several rolls on them, or a
+    /// number, run it and return the accepted outcome expressed as a single line, along with the
+    /// names of the choices that took part in it. Since there is only one outcome, `count` must
+    /// be 1.
+    ///
+    /// Returns `None` for the models that pick choices.
+    fn pick_outcome(
+        &mut self,
+        category: &str,
+        config_category: &mut config::ConfigCategory,
+        eligible: &[bool],
+        count: usize,
+    ) -> Option<Result<(String, Vec<String>), PickError<'static>>> {
+        if !matches!(
+            config_category,
+            config::ConfigCategory::Bundle { .. }
+                | config::ConfigCategory::Dice { .. }
+                | config::ConfigCategory::FloatRange { .. }
+                | config::ConfigCategory::IntegerRange { .. }
+                | config::ConfigCategory::Loot { .. }
+                | config::ConfigCategory::Pairs { .. }
+                | config::ConfigCategory::SecretSanta { .. }
+                | config::ConfigCategory::Teams { .. }
+        ) {
+            return None;
+        }
+        if count > 1 {
+            return Some(Err(PickError::NotEnoughChoices {
+                category: category.to_string(),
+                requested: count,
+                available: 1,
+            }));
         }
 
-        fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
-            self.try_next_u32().map(|v| v as u64)
+        match config_category {
+            config::ConfigCategory::Bundle {
+                budget, choices, ..
+            } => Some(
+                self.pick_bundle(category, choices, eligible, *budget)
+                    .map(|b| (format_bundle(&b), b.choices)),
+            ),
+            config::ConfigCategory::Dice { roll } => {
+                Some(self.pick_dice(category, roll).map(|x| (x, vec![])))
+            }
+            config::ConfigCategory::FloatRange {
+                min,
+                max,
+                step,
+                distribution,
+            } => Some(
+                self.pick_range(
+                    category,
+                    *min,
+                    *max,
+                    *step,
+                    step.map_or(2, step_decimals),
+                    *distribution,
+                )
+                .map(|x| (x, vec![])),
+            ),
+            config::ConfigCategory::IntegerRange {
+                min,
+                max,
+                step,
+                distribution,
+            } => Some(
+                self.pick_range(
+                    category,
+                    *min as f64,
+                    *max as f64,
+                    Some(*step as f64),
+                    0,
+                    *distribution,
+                )
+                .map(|x| (x, vec![])),
+            ),
+            config::ConfigCategory::Loot {
+                rolls,
+                guaranteed,
+                choices,
+                ..
+            } => Some(
+                self.pick_loot(category, choices, eligible, *rolls, guaranteed)
+                    .map(|(drops, rolled)| (drops.join(", "), rolled)),
+            ),
+            config::ConfigCategory::Pairs {
+                rounds, choices, ..
+            } => Some(
+                self.pick_pairs(category, choices, eligible, rounds)
+                    .map(|p| {
+                        let paired = p.pairs.iter().flat_map(|(a, b)| [a.clone(), b.clone()]);
+                        (
+                            format_pairs(&p.pairs, p.sitting_out.as_deref()),
+                            paired.collect(),
+                        )
+                    }),
+            ),
+            config::ConfigCategory::SecretSanta {
+                avoid_rounds,
+                history,
+                choices,
+                ..
+            } => Some(
+                self.pick_secret_santa(category, choices, eligible, *avoid_rounds, history)
+                    .map(|a| {
+                        let givers = a.iter().map(|(giver, _)| giver.clone()).collect();
+                        (format_assignment(&a), givers)
+                    }),
+            ),
+            config::ConfigCategory::Teams {
+                teams,
+                team_size,
+                together,
+                apart,
+                choices,
+                ..
+            } => Some(
+                self.pick_teams(
+                    category,
+                    choices,
+                    eligible,
+                    team_count(eligible, *teams, *team_size),
+                    together,
+                    apart,
+                )
+                .map(|t| (format_teams(&t), t.concat())),
+            ),
+            _ => None,
         }
+    }
 
-        fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), Self::Error> {
-            let mut left = dst;
-            while left.len() >= 4 {
-                let (l, r) = { left }.split_at_mut(4);
-                left = r;
-                let chunk: [u8; 4] = self.next_u32().to_le_bytes();
-                l.copy_from_slice(&chunk);
+    /// Run the given category's model to pick `count` distinct items, and return the indices of
+    /// the chosen items within the category's choices. Since some models reorder their choices, the
+    /// indices refer to the positions of the chosen items after the category has been updated.
+    ///
+    /// Only the choices that are marked in `eligible` are offered, and the state of the other
+    /// choices is left alone. The `category` name is used in errors.
+    fn pick_category(
+        &mut self,
+        category: &str,
+        config_category: &mut config::ConfigCategory,
+        eligible: &[bool],
+        count: usize,
+    ) -> Result<Vec<usize>, PickError<'static>> {
+        Ok(match config_category {
+            config::ConfigCategory::Bandit { choices, .. } => {
+                self.pick_bandit(choices, eligible, count)
             }
-            let n = left.len();
-            if n > 0 {
-                let chunk: [u8; 4] = self.next_u32().to_le_bytes();
-                left.copy_from_slice(&chunk[..n]);
+            config::ConfigCategory::Composite {
+                combine,
+                factors,
+                choices,
+                ..
+            } => self.pick_composite(choices, eligible, factors, *combine, count),
+            config::ConfigCategory::Deck { choices, .. } => {
+                self.pick_deck(choices, eligible, count)
+            }
+            config::ConfigCategory::Even { choices, .. } => {
+                self.pick_even(choices, eligible, count)
+            }
+            config::ConfigCategory::Gaussian {
+                choices,
+                stddev_scaling_factor,
+                mean,
+                last_item_probability,
+                ..
+            } => self
+                .pick_gaussian(
+                    choices,
+                    eligible,
+                    *stddev_scaling_factor,
+                    *mean,
+                    *last_item_probability,
+                    count,
+                )
+                .ok_or_else(|| PickError::InvalidGaussian(category.to_string()))?,
+            config::ConfigCategory::Geometric { p, choices, .. } => self.pick_by_position(
+                choices,
+                eligible,
+                &|position, _| (1.0 - *p).powi(position as i32),
+                count,
+            ),
+            config::ConfigCategory::Inventory { choices, .. } => {
+                self.pick_inventory(choices, eligible, count)
+            }
+            config::ConfigCategory::Lfu {
+                exponent,
+                decay,
+                reset_at,
+                choices,
+                ..
+            } => self.pick_lfu(choices, eligible, *exponent, *decay, *reset_at, count),
+            config::ConfigCategory::Linear { choices, .. } => self.pick_by_position(
+                choices,
+                eligible,
+                &|position, len| (len - position) as f64,
+                count,
+            ),
+            config::ConfigCategory::Lottery { choices, .. } => {
+                self.pick_lottery(choices, eligible, count)
             }
+            config::ConfigCategory::Lru { choices, .. } => self.pick_lru(choices, eligible, count),
+            config::ConfigCategory::Markov { last, choices, .. } => {
+                self.pick_markov(choices, last, eligible, count)
+            }
+            config::ConfigCategory::Quota {
+                tolerance, choices, ..
+            } => self.pick_quota(choices, eligible, *tolerance, count),
+            config::ConfigCategory::Rated { choices, .. } => {
+                self.pick_rated(choices, eligible, count)
+            }
+            config::ConfigCategory::Recency { curve, choices, .. } => {
+                self.pick_recency(choices, eligible, *curve, count)
+            }
+            config::ConfigCategory::SpacedRepetition { choices, .. } => {
+                self.pick_spaced_repetition(choices, eligible, count)
+            }
+            config::ConfigCategory::Bundle { .. }
+            | config::ConfigCategory::Dice { .. }
+            | config::ConfigCategory::FloatRange { .. }
+            | config::ConfigCategory::IntegerRange { .. }
+            | config::ConfigCategory::Loot { .. }
+            | config::ConfigCategory::Pairs { .. }
+            | config::ConfigCategory::SecretSanta { .. }
+            | config::ConfigCategory::Teams { .. } => {
+                unreachable!("categories that produce a single outcome are run by pick_outcome()")
+            }
+            config::ConfigCategory::Stratified { .. } => {
+                unreachable!("stratified categories are run by pick_groups()")
+            }
+            config::ConfigCategory::Weighted { choices, .. } => {
+                self.pick_weighted(choices, eligible, count)
+            }
+            config::ConfigCategory::Zipf {
+                exponent, choices, ..
+            } => self.pick_by_position(
+                choices,
+                eligible,
+                &|position, _| 1.0 / ((position + 1) as f64).powf(*exponent),
+                count,
+            ),
+        })
+    }
 
-            Ok(())
-        }
+    /// Return whether a choice with the given options matches the engine's tag filter.
+    fn matches_tags(&self, options: &config::ChoiceOptions) -> bool {
+        self.tags.iter().all(|t| options.tags.contains(t))
+            && !self.not_tags.iter().any(|t| options.tags.contains(t))
     }
 
-    mock! {
-        pub Ui {}
+    /// Return why a choice with the given options can't be offered right now, or `None` if it
+    /// can be.
+    fn exclusion_reason(&self, options: &config::ChoiceOptions) -> Option<String> {
+        if let Some(schedule) = &options.schedule
+            && !schedule.includes(self.now.naive_local())
+        {
+            return Some("unavailable".to_string());
+        }
 
-        impl ui::Ui for Ui {
-            fn call_display_table(&self) -> bool;
+        match &options.cooling {
+            Some(cooling) if cooling.picks > 0 => Some(format!(
+                "cooling down for {} more pick{}",
+                cooling.picks,
+                if cooling.picks == 1 { "" } else { "s" }
+            )),
+            Some(config::Cooling {
+                until: Some(until), ..
+            }) if *until > self.now => Some(format!(
+                "cooling down until {}",
+                until.format("%Y-%m-%d %H:%M")
+            )),
+            _ => None,
+        }
+    }
 
-            fn display_table<'a>(&self, table: &ui::Table<'a>);
+    /// Update the cooldowns of the given category's choices after the choices at the `picked`
+    /// indices have been accepted, counting as `picks` picks.
+    ///
+    /// The accepted choices start cooling down, and the other choices that are marked in
+    /// `matched` count down the picks that they are cooling down for. Cooldowns that have run
+    /// out are removed.
+    fn update_cooldowns(
+        &self,
+        category: &mut config::ConfigCategory,
+        matched: &[bool],
+        picked: &[usize],
+        picks: u64,
+    ) {
+        let default_cooldown = category_cooldown(category);
 
-            fn info(&self, message: &str);
+        for (i, choice) in category_choices_mut(category).into_iter().enumerate() {
+            let options = choice.options_mut();
+            if picked.contains(&i) {
+                let cooldown = options.cooldown.or(default_cooldown).unwrap_or_default();
+                let duration = chrono::Duration::days(cooldown.days as i64)
+                    + chrono::Duration::hours(cooldown.hours as i64);
+                options.cooling = Some(config::Cooling {
+                    picks: cooldown.picks,
+                    until: (duration > chrono::Duration::zero()).then(|| self.now + duration),
+                });
+            } else if let Some(cooling) = options.cooling.as_mut()
+                && matched[i]
+            {
+                cooling.picks = cooling.picks.saturating_sub(picks);
+                if cooling.until.is_some_and(|u| u <= self.now) {
+                    cooling.until = None;
+                }
+            }
 
-            fn prompt_choice(&self, choice: &str) -> bool;
+            if options.cooling == Some(config::Cooling::default()) {
+                options.cooling = None;
+            }
         }
     }
 
-    #[test]
-    fn test_get_consent() {
-        let mut ui = MockUi::default();
-        ui.expect_prompt_choice()
-            .with(predicate::in_iter(vec![
-                "you want this",
-                "you don't want this",
-            ]))
-            .times(2)
-            .returning(|x| !x.contains("don't"));
-        let mut engine = Engine::new(&ui);
-
-        assert!(engine.get_consent("you want this"));
-        assert!(!engine.get_consent("you don't want this"));
+    /// Use the given time as the current time, rather than the time the Engine was created.
+    pub fn set_now(&mut self, now: DateTime<FixedOffset>) {
+        self.now = now;
     }
 
-    #[test]
-    fn test_pick() {
-        let mut ui = MockUi::default();
-        ui.expect_call_display_table().times(2).returning(|| false);
-        ui.expect_prompt_choice()
-            .with(predicate::in_iter(vec!["that", "this"]))
-            .times(2)
-            .returning(|c| c == "that");
-        let mut engine = Engine::new(&ui);
-        engine.set_rng(FakeRng(0));
-        let choices = vec![
-            String::from("this"),
-            String::from("that"),
-            String::from("the other"),
-        ];
-        let category = config::ConfigCategory::Even { choices };
-        let mut config = BTreeMap::new();
-        config.insert("things".to_string(), category);
+    /// Only offer choices that have all of the given `tags`, and none of the given `not_tags`.
+    ///
+    /// The filter only applies to the category that is picked from, and not to the categories
+    /// that its choices refer to. Choices that don't match the filter are left untouched.
+    pub fn set_tags(&mut self, tags: Vec<String>, not_tags: Vec<String>) {
+        self.tags = tags;
+        self.not_tags = not_tags;
+    }
 
-        let choice = engine.pick(&mut config, "things").unwrap();
+    /// Use the given random number generator rather than the default.
+    pub fn set_rng<R: 'static + Rng>(&mut self, rng: R) {
+        self.rng = Box::new(rng);
+    }
 
-        assert_eq!(choice, "that");
+    /// Express disapproval to the user.
+    fn express_disapproval(&mut self) {
+        self.ui.info("🤨");
     }
 
-    #[test]
-    fn test_pick_nonexistant_category() {
-        let ui = MockUi::default();
-        let mut engine = Engine::new(&ui);
-        let choices = vec![
-            String::from("this"),
-            String::from("that"),
-            String::from("the other"),
-        ];
-        let category = config::ConfigCategory::Even { choices };
-        let mut config = BTreeMap::new();
-        config.insert("things".to_string(), category);
+    /// Prompt the user for consent for the given choice, returning a bool true if they accept the
+    /// choice, or false if they do not.
+    fn get_consent(&mut self, choice: &str) -> bool {
+        self.ui.prompt_choice(choice)
+    }
 
-        match engine.pick(&mut config, "does not exist") {
-            Ok(_) => {
-                panic!("The non-existant category should have returned an error.");
+    /// Return the indices of the eligible choices of the given category in a random order, as
+    /// described in [`Engine::order`], or `None` if the category's model can't order its choices.
+    fn order_choices(
+        &mut self,
+        category: &config::ConfigCategory,
+        eligible: &[bool],
+    ) -> Option<Vec<usize>> {
+        let candidates = (0..eligible.len())
+            .filter(|i| eligible[*i])
+            .collect::<Vec<_>>();
+        let weights = match category {
+            config::ConfigCategory::Even { .. } => candidates.iter().map(|_| 1).collect(),
+            config::ConfigCategory::Inventory { choices, .. } => {
+                candidates.iter().map(|i| choices[*i].tickets).collect()
             }
-            Err(error) => {
-                assert_eq!(
-                    format!("{}", error),
-                    "The category `does not exist` was not found in the given config."
-                );
+            config::ConfigCategory::Lottery { choices, .. } => {
+                candidates.iter().map(|i| choices[*i].tickets).collect()
+            }
+            config::ConfigCategory::Weighted { choices, .. } => {
+                candidates.iter().map(|i| choices[*i].weight).collect()
             }
+            config::ConfigCategory::Gaussian {
+                stddev_scaling_factor,
+                mean,
+                last_item_probability,
+                ..
+            } => {
+                let mut candidates = candidates;
+                let mut order = Vec::with_capacity(candidates.len());
+                while !candidates.is_empty() {
+                    let len = candidates.len();
+                    let mean = mean.unwrap_or(0.0).clamp(0.0, len as f64);
+                    let stddev = match last_item_probability {
+                        Some(probability) => gaussian_stddev(len, mean, *probability),
+                        None => (len as f64) / stddev_scaling_factor,
+                    };
+                    let chances = gaussian_chances(len, mean, stddev);
+                    let index = WeightedIndex::new(&chances).ok()?.sample(&mut self.rng);
+                    order.push(candidates.remove(index));
+                }
+                return Some(order);
+            }
+            config::ConfigCategory::Lru { .. } => return Some(candidates),
+            _ => return None,
+        };
+
+        Some(weighted_order(candidates, weights, &mut self.rng))
+    }
+
+    /// Run the bandit model for the given choices. Every choice the user rejects has its
+    /// `rejected` count increased, and every choice the user accepts has its `accepted` count
+    /// increased.
+    ///
+    /// The weight of each candidate is found with Thompson sampling: we draw a sample from each
+    /// candidate's Beta posterior many times, and count how often each candidate's sample is the
+    /// highest. This gives each candidate very nearly the chance that a single round of Thompson
+    /// sampling would give it, and lets us show that chance in the table.
+    fn pick_bandit(
+        &mut self,
+        choices: &mut [config::BanditChoice],
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let mut picked = Vec::with_capacity(count);
+        let mut rejected = vec![];
+
+        while picked.len() < count {
+            let candidates = (0..choices.len())
+                .filter(|i| eligible[*i] && !picked.contains(i))
+                .collect::<Vec<_>>();
+            let weights = self.thompson_weights(choices, &candidates);
+            let initialize_candidates = || {
+                candidates
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(i, w)| ((*i, &choices[*i].name), *w))
+                    .collect::<Vec<_>>()
+            };
+            let accepted_cell = |i: usize| ui::Cell::Unsigned(choices[i].accepted);
+            let rejected_cell = |i: usize| ui::Cell::Unsigned(choices[i].rejected);
+
+            let (index, mut slot_rejected) = self.pick_weighted_with_feedback(
+                &initialize_candidates,
+                &[("Accepted", &accepted_cell), ("Rejected", &rejected_cell)],
+            );
+            picked.push(index);
+            rejected.append(&mut slot_rejected);
+        }
+
+        for index in rejected.iter() {
+            choices[*index].rejected += 1;
         }
+        for index in picked.iter() {
+            choices[*index].accepted += 1;
+        }
+
+        picked
     }
 
-    #[test]
-    fn test_pick_even() {
-        let mut ui = MockUi::default();
-        ui.expect_call_display_table().times(1).returning(|| false);
-        ui.expect_prompt_choice()
-            .with(predicate::eq("this"))
-            .times(1)
-            .returning(|_| true);
-        let mut engine = Engine::new(&ui);
-        engine.set_rng(FakeRng(0));
-        let choices = vec![
-            String::from("this"),
-            String::from("that"),
-            String::from("the other"),
-        ];
+    /// Return how many of [`THOMPSON_ROUNDS`] rounds of Thompson sampling each of the given
+    /// candidates won.
+    ///
+    /// Every candidate gets one extra win, so that a candidate with a very low chance can still be
+    /// offered once all the others have been rejected.
+    fn thompson_weights(
+        &mut self,
+        choices: &[config::BanditChoice],
+        candidates: &[usize],
+    ) -> Vec<u64> {
+        let posteriors = candidates
+            .iter()
+            .map(|i| {
+                let choice = &choices[*i];
+                Beta::new((choice.accepted + 1) as f64, (choice.rejected + 1) as f64).unwrap()
+            })
+            .collect::<Vec<_>>();
+        let mut wins = vec![1; candidates.len()];
+
+        for _ in 0..THOMPSON_ROUNDS {
+            let samples = posteriors
+                .iter()
+                .map(|p| p.sample(&mut self.rng))
+                .collect::<Vec<f64>>();
+            let winner = (0..samples.len())
+                .max_by(|a, b| samples[*a].total_cmp(&samples[*b]))
+                .unwrap();
+            wins[winner] += 1;
+        }
+
+        wins
+    }
+
+    /// Run a position decay model for the given choices. The `weight()` function is given a
+    /// candidate's position among the remaining candidates and the number of remaining candidates,
+    /// and returns the candidate's weight. Candidates with a weight of 0 are not offered. When the
+    /// user accepts a choice, that choice is moved to the end of the choices Vector.
+    fn pick_by_position(
+        &mut self,
+        choices: &mut Vec<config::Choice>,
+        eligible: &[bool],
+        weight: &dyn Fn(usize, usize) -> f64,
+        count: usize,
+    ) -> Vec<usize> {
+        let initialize_candidates = |picked: &[usize]| {
+            let remaining = (0..choices.len())
+                .filter(|i| eligible[*i] && !picked.contains(i))
+                .collect::<Vec<_>>();
+            remaining
+                .iter()
+                .enumerate()
+                .map(|(position, i)| ((*i, &choices[*i].name), weight(position, remaining.len())))
+                .filter(|x| x.1 > 0.0)
+                .collect::<Vec<_>>()
+        };
+
+        let picked = self.pick_weighted_distinct(count, &initialize_candidates, &[]);
+
+        move_to_end(choices, &picked);
+
+        (choices.len() - count..choices.len()).collect()
+    }
+
+    /// Run the composite model for the given choices. Each choice's weight is combined from the
+    /// given factors, and the factors' states are updated for each accepted choice in the order
+    /// they were accepted. If there is an lru factor, the accepted choices are moved to the end of
+    /// the choices Vector.
+    fn pick_composite(
+        &mut self,
+        choices: &mut Vec<config::CompositeChoice>,
+        eligible: &[bool],
+        factors: &[config::CompositeFactor],
+        combine: config::Combine,
+        count: usize,
+    ) -> Vec<usize> {
+        let values = factors
+            .iter()
+            .map(|f| composite_factor(choices, eligible, f))
+            .collect::<Vec<_>>();
+        let weights = composite_weights(choices, eligible, factors, combine);
+
+        let initialize_candidates = |picked: &[usize]| {
+            choices
+                .iter()
+                .enumerate()
+                .filter(|x| eligible[x.0] && weights[x.0] > 0.0 && !picked.contains(&x.0))
+                .map(|x| ((x.0, &x.1.name), weights[x.0]))
+                .collect::<Vec<_>>()
+        };
+        let cells = values
+            .iter()
+            .map(|v| move |i: usize| ui::Cell::Decimal(v[i]))
+            .collect::<Vec<_>>();
+        let mut columns: Vec<Column> = vec![];
+        for (factor, cell) in factors.iter().zip(cells.iter()) {
+            columns.push((composite_factor_label(factor), cell));
+        }
+
+        let picked = self.pick_weighted_distinct(count, &initialize_candidates, &columns);
 
-        let result = engine.pick_even(&choices);
+        for factor in factors.iter() {
+            if let config::CompositeFactor::Lottery { increment, reset } = factor {
+                for index in picked.iter() {
+                    for (choice, _) in choices.iter_mut().zip(eligible).filter(|(_, e)| **e) {
+                        choice.tickets += increment;
+                    }
+                    choices[*index].tickets = *reset;
+                }
+            }
+        }
 
-        assert_eq!(result, "this");
+        if factors
+            .iter()
+            .any(|f| matches!(f, config::CompositeFactor::Lru { .. }))
+        {
+            move_to_end(choices, &picked);
+            return (choices.len() - count..choices.len()).collect();
+        }
+
+        picked
+    }
+
+    /// Run the deck model for the given choices. Choices are drawn evenly from those that are
+    /// still in the deck, and accepted choices are removed from the deck. If none of the eligible
+    /// choices are left in the deck, the eligible choices are reshuffled into it first.
+    fn pick_deck(
+        &mut self,
+        choices: &mut [config::DeckChoice],
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let mut in_deck = choices.iter().map(|c| !c.drawn).collect::<Vec<_>>();
+        let mut picked = Vec::with_capacity(count);
+
+        while picked.len() < count {
+            if !(0..choices.len()).any(|i| eligible[i] && in_deck[i]) {
+                for (d, _) in in_deck.iter_mut().zip(eligible).filter(|(_, e)| **e) {
+                    *d = true;
+                }
+                self.ui
+                    .info("The deck is empty, so it has been reshuffled.");
+            }
+
+            let initialize_candidates = || {
+                (0..choices.len())
+                    .filter(|i| eligible[*i] && in_deck[*i] && !picked.contains(i))
+                    .collect::<Vec<_>>()
+            };
+            let mut candidates = initialize_candidates();
+
+            let chosen = loop {
+                let index = *candidates.choose(&mut self.rng).unwrap();
+
+                if self.ui.call_display_table() {
+                    self.display_deck_table(index, choices, &in_deck, &candidates);
+                }
+
+                if self.get_consent(&choices[index].name[..]) {
+                    break index;
+                } else if candidates.len() > 1 {
+                    candidates.retain(|c| *c != index);
+                } else {
+                    self.express_disapproval();
+                    candidates = initialize_candidates();
+                }
+            };
+            in_deck[chosen] = false;
+            picked.push(chosen);
+        }
+
+        for (choice, in_deck) in choices.iter_mut().zip(in_deck) {
+            choice.drawn = !in_deck;
+        }
+
+        picked
+    }
+
+    /// Use an even distribution random model to pick from the given choices.
+    fn pick_even(
+        &mut self,
+        choices: &[config::Choice],
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let initialize_candidates = |picked: &[usize]| {
+            choices
+                .iter()
+                .enumerate()
+                .filter(|x| eligible[x.0] && !picked.contains(&x.0))
+                .map(|x| ((x.0, &x.1.name), 1))
+                .collect::<Vec<_>>()
+        };
+
+        self.pick_weighted_distinct(count, &initialize_candidates, &[])
+    }
+
+    /// Run the gaussian model for the given choices and standard deviation scaling factor. Each
+    /// candidate is picked with the exact chance given by [`gaussian_chances`], so every pick takes
+    /// a single draw. When the user accepts a choice, move that choice to end of the choices Vector
+    /// and return.
+    ///
+    /// The `mean` is clamped to the list of candidates, and defaults to the beginning of the list.
+    /// If `last_item_probability` is given, it is used to derive the standard deviation instead of
+    /// `stddev_scaling_factor`. The parameters should have been validated by [`check_parameters`],
+    /// and `None` is returned if they still don't give usable chances.
+    fn pick_gaussian(
+        &mut self,
+        choices: &mut Vec<config::Choice>,
+        eligible: &[bool],
+        stddev_scaling_factor: f64,
+        mean: Option<f64>,
+        last_item_probability: Option<f64>,
+        count: usize,
+    ) -> Option<Vec<usize>> {
+        let initialize_candidates = |picked: &[usize]| {
+            (0..choices.len())
+                .filter(|i| eligible[*i] && !picked.contains(i))
+                .collect::<Vec<_>>()
+        };
+        let mut picked = Vec::with_capacity(count);
+
+        while picked.len() < count {
+            let mut candidates = initialize_candidates(&picked);
+
+            let chosen = loop {
+                let len = candidates.len();
+                let mean = mean.unwrap_or(0.0).clamp(0.0, len as f64);
+                let stddev = match last_item_probability {
+                    Some(probability) => gaussian_stddev(len, mean, probability),
+                    None => (len as f64) / stddev_scaling_factor,
+                };
+                let chances = gaussian_chances(len, mean, stddev);
+                let index = WeightedIndex::new(&chances).ok()?.sample(&mut self.rng);
+                let value = candidates[index];
+
+                if self.ui.call_display_table() {
+                    let names = candidates
+                        .iter()
+                        .map(|i| &choices[*i].name)
+                        .collect::<Vec<_>>();
+                    self.display_gaussian_chance_table(index, &names, &chances, mean, stddev);
+                }
+
+                if self.get_consent(&choices[value].name[..]) {
+                    break value;
+                } else if candidates.len() > 1 {
+                    candidates.remove(index);
+                } else {
+                    self.express_disapproval();
+                    candidates = initialize_candidates(&picked);
+                }
+            };
+            picked.push(chosen);
+        }
+
+        move_to_end(choices, &picked);
+
+        Some((choices.len() - count..choices.len()).collect())
+    }
+
+    /// Run the inventory model for the given choices.
+    fn pick_inventory(
+        &mut self,
+        choices: &mut [config::InventoryChoice],
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let initialize_candidates = |picked: &[usize]| {
+            choices
+                .iter()
+                .enumerate()
+                .filter(|x| eligible[x.0] && x.1.tickets > 0 && !picked.contains(&x.0))
+                .map(|x| ((x.0, &x.1.name), x.1.tickets))
+                .collect::<Vec<_>>()
+        };
+
+        let picked = self.pick_weighted_distinct(count, &initialize_candidates, &[]);
+
+        for index in picked.iter() {
+            choices[*index].tickets -= 1;
+        }
+
+        picked
+    }
+
+    /// Run the Lru model for the given choices. When the user accepts a choice, move that choice to
+    /// the end of the choices Vector and return.
+    fn pick_lru(
+        &mut self,
+        choices: &mut Vec<config::Choice>,
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let mut picked = Vec::with_capacity(count);
+
+        while picked.len() < count {
+            let candidates = (0..choices.len())
+                .filter(|i| eligible[*i] && !picked.contains(i))
+                .collect::<Vec<_>>();
+
+            let chosen = candidates.iter().enumerate().find_map(|(i, &index)| {
+                if self.ui.call_display_table() {
+                    let names = candidates[i..]
+                        .iter()
+                        .map(|c| &choices[*c].name)
+                        .collect::<Vec<_>>();
+                    self.display_lru_table(&names);
+                }
+
+                self.get_consent(&choices[index].name[..]).then_some(index)
+            });
+
+            match chosen {
+                Some(index) => picked.push(index),
+                // If we've gotten here, the user hasn't made a choice. So… let's do it again!
+                None => self.express_disapproval(),
+            }
+        }
+
+        move_to_end(choices, &picked);
+
+        (choices.len() - count..choices.len()).collect()
+    }
+
+    /// Run the lottery model for the given choices. Only the eligible choices take part in the
+    /// lottery, so the other choices don't receive any tickets.
+    fn pick_lottery(
+        &mut self,
+        choices: &mut [config::LotteryChoice],
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let initialize_candidates = |picked: &[usize]| {
+            choices
+                .iter()
+                .enumerate()
+                .filter(|x| eligible[x.0] && x.1.tickets > 0 && !picked.contains(&x.0))
+                .map(|x| ((x.0, &x.1.name), x.1.tickets))
+                .collect::<Vec<_>>()
+        };
+
+        let picked = self.pick_weighted_distinct(count, &initialize_candidates, &[]);
+
+        // Each pick runs a full round of the lottery, just as if they had been picked one at a time.
+        for index in picked.iter() {
+            for (choice, _) in choices.iter_mut().zip(eligible).filter(|(_, e)| **e) {
+                choice.tickets += choice.weight;
+            }
+            choices[*index].tickets = choices[*index].reset;
+        }
+
+        picked
+    }
+
+    /// Run the markov model for the given choices. Each pick uses the transition weights of the
+    /// choice that was accepted before it, and `last` is set to the last accepted choice.
+    ///
+    /// If the last choice is unknown, or none of the remaining candidates may follow it, the
+    /// choices' own weights are used instead, and if none of those are positive either, the
+    /// candidates are picked from evenly.
+    fn pick_markov(
+        &mut self,
+        choices: &[config::MarkovChoice],
+        last: &mut Option<String>,
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let mut picked = Vec::with_capacity(count);
+
+        while picked.len() < count {
+            let state = last
+                .as_ref()
+                .and_then(|l| choices.iter().find(|c| &c.name == l));
+            let remaining = (0..choices.len())
+                .filter(|i| eligible[*i] && !picked.contains(i))
+                .collect::<Vec<_>>();
+            let weights_from = |state: Option<&config::MarkovChoice>| {
+                remaining
+                    .iter()
+                    .map(|i| {
+                        let weight = choices[*i].weight;
+                        state.map_or(weight, |s| {
+                            *s.transitions.get(&choices[*i].name).unwrap_or(&weight)
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            };
+            let (state, weights) = match weights_from(state) {
+                weights if weights.iter().any(|w| *w > 0) => (state, weights),
+                _ => match weights_from(None) {
+                    weights if weights.iter().any(|w| *w > 0) => (None, weights),
+                    weights => (None, weights.iter().map(|_| 1).collect()),
+                },
+            };
+
+            let initialize_candidates = || {
+                remaining
+                    .iter()
+                    .zip(weights.iter())
+                    .filter(|(_, w)| **w > 0)
+                    .map(|(i, w)| ((*i, &choices[*i].name), *w))
+                    .collect::<Vec<_>>()
+            };
+            let from_cell = |_: usize| match state {
+                Some(state) => ui::Cell::from(state.name.as_str()),
+                None => ui::Cell::Text("(none)"),
+            };
+            let index = self.pick_weighted_common(&initialize_candidates, &[("From", &from_cell)]);
+
+            *last = Some(choices[index].name.clone());
+            picked.push(index);
+        }
+
+        picked
+    }
+
+    /// Run the quota model for the given choices. The candidates are offered in order of how far
+    /// behind their target share they are, picking evenly among the candidates whose deficits are
+    /// within `tolerance` of the largest. When the user accepts a choice, its `picks` count is
+    /// increased by 1.
+    fn pick_quota(
+        &mut self,
+        choices: &mut [config::QuotaChoice],
+        eligible: &[bool],
+        tolerance: f64,
+        count: usize,
+    ) -> Vec<usize> {
+        let total_target = choices.iter().map(|c| c.target).sum::<u64>().max(1) as f64;
+        let mut picks = choices.iter().map(|c| c.picks).collect::<Vec<_>>();
+        let mut picked = Vec::with_capacity(count);
+
+        while picked.len() < count {
+            // The deficit is how many picks a choice is behind its target share, counting the
+            // pick that we are about to make.
+            let total = (picks.iter().sum::<u64>() + 1) as f64;
+            let deficits = choices
+                .iter()
+                .zip(picks.iter())
+                .map(|(c, p)| c.target as f64 / total_target * total - *p as f64)
+                .collect::<Vec<_>>();
+            let initialize_candidates = || {
+                let mut candidates = (0..choices.len())
+                    .filter(|i| eligible[*i] && !picked.contains(i))
+                    .collect::<Vec<_>>();
+                candidates.sort_by(|a, b| deficits[*b].total_cmp(&deficits[*a]));
+                candidates
+            };
+            let mut candidates = initialize_candidates();
+
+            let chosen = loop {
+                let largest = deficits[candidates[0]];
+                let tied = candidates
+                    .iter()
+                    .take_while(|i| deficits[**i] >= largest - tolerance)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                let index = *tied.choose(&mut self.rng).unwrap();
+
+                if self.ui.call_display_table() {
+                    self.display_quota_table(
+                        index,
+                        choices,
+                        &picks,
+                        &deficits,
+                        &candidates,
+                        tied.len(),
+                    );
+                }
+
+                if self.get_consent(&choices[index].name[..]) {
+                    break index;
+                } else if candidates.len() > 1 {
+                    candidates.retain(|c| *c != index);
+                } else {
+                    self.express_disapproval();
+                    candidates = initialize_candidates();
+                }
+            };
+            picks[chosen] += 1;
+            picked.push(chosen);
+        }
+
+        for (choice, picks) in choices.iter_mut().zip(picks) {
+            choice.picks = picks;
+        }
+
+        picked
+    }
+
+    /// Run the lfu model for the given choices. Without an `exponent`, the choice with the lowest
+    /// count is always picked, and choices that are tied for the lowest count are picked from
+    /// evenly. With an `exponent`, each choice's weight is `1 / (count + 1)^exponent`, treating a
+    /// negative count as 0. Either way, the counts are updated for each accepted choice in the
+    /// order they were accepted, and `decay` and `reset_at` only change the eligible choices.
+    fn pick_lfu(
+        &mut self,
+        choices: &mut [config::LfuChoice],
+        eligible: &[bool],
+        exponent: Option<f64>,
+        decay: Option<f64>,
+        reset_at: Option<f64>,
+        count: usize,
+    ) -> Vec<usize> {
+        let mut counts = choices.iter().map(|c| c.count).collect::<Vec<_>>();
+        let mut picked = Vec::with_capacity(count);
+
+        while picked.len() < count {
+            let initialize_candidates = || {
+                (0..choices.len())
+                    .filter(|i| eligible[*i] && !picked.contains(i))
+                    .collect::<Vec<_>>()
+            };
+            let mut candidates = initialize_candidates();
+
+            let chosen = loop {
+                let lowest = candidates
+                    .iter()
+                    .map(|i| counts[*i])
+                    .fold(f64::INFINITY, f64::min);
+                let weighted = candidates
+                    .iter()
+                    .map(|i| {
+                        let weight = match exponent {
+                            Some(exponent) => 1.0 / (counts[*i].max(0.0) + 1.0).powf(exponent),
+                            None if counts[*i] <= lowest => 1.0,
+                            None => 0.0,
+                        };
+                        ((*i, &choices[*i].name), weight)
+                    })
+                    .collect::<Vec<_>>();
+                let (index, _) = weighted
+                    .choose_weighted(&mut self.rng, |item| item.1)
+                    .unwrap()
+                    .0;
+
+                if self.ui.call_display_table() {
+                    let count_cell = |i: usize| ui::Cell::Decimal(counts[i]);
+                    self.display_weighted_chance_table(index, &weighted, &[("Count", &count_cell)]);
+                }
+
+                if self.get_consent(&choices[index].name[..]) {
+                    break index;
+                } else if candidates.len() > 1 {
+                    candidates.retain(|c| *c != index);
+                } else {
+                    self.express_disapproval();
+                    candidates = initialize_candidates();
+                }
+            };
+
+            if let Some(decay) = decay {
+                for (c, _) in counts.iter_mut().zip(eligible).filter(|(_, e)| **e) {
+                    *c *= decay.clamp(0.0, 1.0);
+                }
+            }
+            counts[chosen] += 1.0;
+            if reset_at.is_some_and(|reset_at| counts[chosen] >= reset_at) {
+                for (c, _) in counts.iter_mut().zip(eligible).filter(|(_, e)| **e) {
+                    *c = 0.0;
+                }
+            }
+            picked.push(chosen);
+        }
+
+        for (choice, count) in choices.iter_mut().zip(counts) {
+            choice.count = count;
+        }
+
+        picked
+    }
+
+    /// Run the rated model for the given choices, using their ratings as weights.
+    fn pick_rated(
+        &mut self,
+        choices: &[config::RatedChoice],
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let initialize_candidates = |picked: &[usize]| {
+            choices
+                .iter()
+                .enumerate()
+                .filter(|x| eligible[x.0] && x.1.rating > 0.0 && !picked.contains(&x.0))
+                .map(|x| ((x.0, &x.1.name), x.1.rating))
+                .collect::<Vec<_>>()
+        };
+
+        self.pick_weighted_distinct(count, &initialize_candidates, &[])
+    }
+
+    /// Run the recency model for the given choices. When the user accepts a choice, its
+    /// `last_picked` time is set to now.
+    fn pick_recency(
+        &mut self,
+        choices: &mut [config::RecencyChoice],
+        eligible: &[bool],
+        curve: config::RecencyCurve,
+        count: usize,
+    ) -> Vec<usize> {
+        let days_since = choices
+            .iter()
+            .map(|c| {
+                c.last_picked
+                    .map(|t| ((self.now - t).num_seconds() as f64 / SECONDS_PER_DAY).max(0.0))
+            })
+            .collect::<Vec<_>>();
+        // Choices that have never been picked are treated as if they were picked at the same time
+        // as the least recently picked choice.
+        let never = days_since.iter().flatten().cloned().fold(0.0, f64::max);
+        let weights = days_since
+            .iter()
+            .map(|d| recency_weight(curve, d.unwrap_or(never)))
+            .collect::<Vec<_>>();
+
+        let initialize_candidates = |picked: &[usize]| {
+            choices
+                .iter()
+                .enumerate()
+                .filter(|x| eligible[x.0] && !picked.contains(&x.0))
+                .map(|x| ((x.0, &x.1.name), weights[x.0]))
+                .collect::<Vec<_>>()
+        };
+        let days_since_cell = |i: usize| match days_since[i] {
+            Some(days) => ui::Cell::Decimal(days),
+            None => ui::Cell::Text("never"),
+        };
+
+        let picked = self.pick_weighted_distinct(
+            count,
+            &initialize_candidates,
+            &[("Days Since", &days_since_cell)],
+        );
+
+        for index in picked.iter() {
+            choices[*index].last_picked = Some(self.now);
+        }
+
+        picked
+    }
+
+    /// Run the spaced repetition model for the given choices, preferring the choices that are most
+    /// overdue. Once the choices have been picked, the user grades how well they recalled each of
+    /// them, in the order they were picked, and each choice is rescheduled with its grade.
+    fn pick_spaced_repetition(
+        &mut self,
+        choices: &mut [config::SpacedRepetitionChoice],
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let due = choices
+            .iter()
+            .map(|c| match c.due {
+                Some(due) => due.format("%Y-%m-%d %H:%M").to_string(),
+                None => "new".to_string(),
+            })
+            .collect::<Vec<_>>();
+        let weights = choices
+            .iter()
+            .map(|c| {
+                let days_overdue = c
+                    .due
+                    .map(|d| (self.now - d).num_seconds() as f64 / SECONDS_PER_DAY)
+                    .unwrap_or(0.0);
+                spaced_repetition_weight(days_overdue)
+            })
+            .collect::<Vec<_>>();
+
+        let initialize_candidates = |picked: &[usize]| {
+            choices
+                .iter()
+                .enumerate()
+                .filter(|x| eligible[x.0] && !picked.contains(&x.0))
+                .map(|x| ((x.0, &x.1.name), weights[x.0]))
+                .collect::<Vec<_>>()
+        };
+        let due_cell = |i: usize| ui::Cell::Text(&due[i]);
+        let interval_cell = |i: usize| ui::Cell::Unsigned(choices[i].interval);
+        let ease_cell = |i: usize| ui::Cell::Decimal(choices[i].ease);
+
+        let picked = self.pick_weighted_distinct(
+            count,
+            &initialize_candidates,
+            &[
+                ("Due", &due_cell),
+                ("Interval", &interval_cell),
+                ("Ease", &ease_cell),
+            ],
+        );
+
+        for index in picked.iter() {
+            let grade = self.ui.prompt_grade(&choices[*index].name);
+            review(&mut choices[*index], grade, self.now);
+        }
+
+        picked
+    }
+
+    /// Run the dice model for the given dice roll, rolling each die and adding them up.
+    fn pick_dice(&mut self, category: &str, roll: &str) -> Result<String, PickError<'static>> {
+        let Some((dice, modifier)) = parse_dice(roll) else {
+            return Err(PickError::InvalidDice {
+                category: category.to_string(),
+                roll: roll.to_string(),
+            });
+        };
+
+        loop {
+            let mut total = modifier;
+            for (count, sides) in dice.iter() {
+                for _ in 0..count.unsigned_abs() {
+                    let value = self.rng.random_range(1..=*sides) as i64;
+                    total += value * count.signum();
+                }
+            }
+
+            if self.ui.call_display_table() {
+                let (lowest, chances) = dice_chances(&dice, modifier);
+                let size = chances.len().div_ceil(DISTRIBUTION_ROWS);
+                let rows = chances
+                    .chunks(size)
+                    .enumerate()
+                    .map(|(i, c)| {
+                        let first = lowest + (i * size) as i64;
+                        let last = first + c.len() as i64 - 1;
+                        let label = span_label(first.to_string(), last.to_string());
+                        (label, c.iter().sum())
+                    })
+                    .collect::<Vec<_>>();
+                self.display_distribution_table(&rows, (total - lowest) as usize / size);
+            }
+
+            let total = total.to_string();
+            if self.get_consent(&total) {
+                break Ok(total);
+            }
+        }
+    }
+
+    /// Run the range models, picking a number between `min` and `max` from the given
+    /// distribution, and expressing it with the given number of decimal places.
+    ///
+    /// If there is a `step`, each multiple of the step past `min` covers the part of the number
+    /// line that is closer to it than to its neighbours. The number is drawn from the whole
+    /// covered line, and rounded to the nearest multiple, so each multiple gets the chance of the
+    /// distribution landing on its part of the line. The normal distribution is drawn with its
+    /// inverse cumulative distribution function, so every pick takes a single draw.
+    fn pick_range(
+        &mut self,
+        category: &str,
+        min: f64,
+        max: f64,
+        step: Option<f64>,
+        decimals: usize,
+        distribution: config::RangeDistribution,
+    ) -> Result<String, PickError<'static>> {
+        let invalid = || Err(PickError::InvalidRange(category.to_string()));
+        if min > max || step.is_some_and(|s| s <= 0.0) {
+            return invalid();
+        }
+        let count = step.map_or(1, |s| ((max - min) / s + 1e-9).floor() as usize + 1);
+        let value = |k: usize| min + k as f64 * step.unwrap_or(0.0);
+        let half = step.unwrap_or(0.0) / 2.0;
+        let (low, high) = match step {
+            Some(_) => (min - half, value(count - 1) + half),
+            None => (min, max),
+        };
+        let cdf: Option<Box<dyn ContinuousCDF<f64, f64>>> = if low < high {
+            match distribution {
+                config::RangeDistribution::Uniform => Some(Box::new(
+                    statrs::distribution::Uniform::new(low, high).unwrap(),
+                )),
+                config::RangeDistribution::Normal { mean, stddev } => {
+                    let stddev = stddev.unwrap_or((high - low) / 6.0);
+                    let mean = mean.unwrap_or((min + max) / 2.0);
+                    match statrs::distribution::Normal::new(mean, stddev) {
+                        Ok(normal) if stddev > 0.0 => Some(Box::new(normal)),
+                        _ => return invalid(),
+                    }
+                }
+            }
+        } else {
+            None
+        };
+        // A mean that is too far from the range leaves the range without any chance of being
+        // reached.
+        if cdf
+            .as_ref()
+            .is_some_and(|cdf| cdf.cdf(high) <= cdf.cdf(low))
+        {
+            return invalid();
+        }
+        let chance = |from: f64, to: f64| match &cdf {
+            Some(cdf) => (cdf.cdf(to) - cdf.cdf(from)) / (cdf.cdf(high) - cdf.cdf(low)),
+            None => 1.0,
+        };
+        let label = |x: f64| format!("{:.*}", decimals, x);
+
+        loop {
+            let x = match &cdf {
+                Some(cdf) => {
+                    let (start, end) = (cdf.cdf(low), cdf.cdf(high));
+                    let u = start + self.rng.random::<f64>() * (end - start);
+                    cdf.inverse_cdf(u).clamp(low, high)
+                }
+                None => low,
+            };
+            let (x, chosen) = match step {
+                Some(s) => {
+                    let k = ((x - min) / s).round().clamp(0.0, (count - 1) as f64) as usize;
+                    (value(k), k / count.div_ceil(DISTRIBUTION_ROWS))
+                }
+                None => {
+                    let width = (max - min) / DISTRIBUTION_ROWS as f64;
+                    let bin = ((x - min) / width).floor() as usize;
+                    (x, bin.min(DISTRIBUTION_ROWS - 1))
+                }
+            };
+
+            if self.ui.call_display_table() {
+                let rows = match step {
+                    Some(_) => {
+                        let size = count.div_ceil(DISTRIBUTION_ROWS);
+                        (0..count)
+                            .step_by(size)
+                            .map(|first| {
+                                let last = (first + size).min(count) - 1;
+                                (
+                                    span_label(label(value(first)), label(value(last))),
+                                    chance(value(first) - half, value(last) + half),
+                                )
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    None if low < high => {
+                        let width = (max - min) / DISTRIBUTION_ROWS as f64;
+                        (0..DISTRIBUTION_ROWS)
+                            .map(|i| {
+                                let (from, to) =
+                                    (min + i as f64 * width, min + (i + 1) as f64 * width);
+                                (span_label(label(from), label(to)), chance(from, to))
+                            })
+                            .collect()
+                    }
+                    None => vec![(label(min), 1.0)],
+                };
+                self.display_distribution_table(&rows, chosen);
+            }
+
+            let x = label(x);
+            if self.get_consent(&x) {
+                break Ok(x);
+            }
+        }
+    }
+
+    /// Run the loot model for the given choices, rolling `rolls` times on the eligible choices.
+    ///
+    /// Each roll is a single weighted pick from everything that the roll could land on, as found
+    /// by [`loot_outcomes`], weighted by the chance of reaching it through the nested tables.
+    /// Landing on a choice that rerolls its table queues up that many more rolls on the table. Once
+    /// every roll has been accepted, all of the drops are shown to the user.
+    ///
+    /// Returns the drops, along with the names of the category's own choices that the rolls on
+    /// the category's table landed on or passed through.
+    fn pick_loot(
+        &mut self,
+        category: &str,
+        choices: &[config::LootChoice],
+        eligible: &[bool],
+        rolls: u64,
+        guaranteed: &[String],
+    ) -> Result<(Vec<String>, Vec<String>), PickError<'static>> {
+        if rolls > MAX_ROLLS || max_reroll(choices) > MAX_ROLLS {
+            return Err(PickError::TooManyRolls(category.to_string()));
+        }
+        let mut outcomes = vec![];
+        loot_outcomes(choices, Some(eligible), true, 1.0, "", &mut outcomes);
+        if outcomes.is_empty() && rolls > 0 {
+            return Err(PickError::NotEnoughChoices {
+                category: category.to_string(),
+                requested: 1,
+                available: 0,
+            });
+        }
+
+        let mut drops = guaranteed.to_vec();
+        let mut rolled = vec![];
+        // Each pending roll is the table to roll on, the names of the tables that lead to it, and
+        // whether its rerolling choices can be rolled.
+        let mut pending = vec![(choices, String::new(), true); rolls as usize];
+        while let Some((table, path, rerolls)) = pending.pop() {
+            let mut outcomes = vec![];
+            let eligible = path.is_empty().then_some(eligible);
+            loot_outcomes(table, eligible, rerolls, 1.0, &path, &mut outcomes);
+            if outcomes.is_empty() {
+                continue;
+            }
+            let initialize_candidates = || {
+                outcomes
+                    .iter()
+                    .enumerate()
+                    .map(|(i, o)| ((i, o.name), o.chance))
+                    .collect::<Vec<_>>()
+            };
+            let cell = |i: usize| ui::Cell::from(outcomes[i].path.as_str());
+
+            let index = self.pick_weighted_common(&initialize_candidates, &[("Table", &cell)]);
+
+            let outcome = &outcomes[index];
+            if path.is_empty() {
+                let root = outcome.path.split(" → ").next().unwrap_or_default();
+                rolled.push(
+                    if root.is_empty() {
+                        outcome.name.as_str()
+                    } else {
+                        root
+                    }
+                    .to_string(),
+                );
+            }
+            if outcome.reroll > 0 {
+                let reroll = (outcome.table, outcome.path.clone(), false);
+                pending.extend(std::iter::repeat_n(reroll, outcome.reroll as usize));
+            } else {
+                drops.push(outcome.name.clone());
+            }
+        }
+        self.ui.info(&format!("Loot: {}", drops.join(", ")));
+
+        Ok((drops, rolled))
+    }
+
+    /// Run the bundle model for the given choices. Choices are added to the bundle one at a time,
+    /// with a weighted distribution over the choices that still fit within what is left of the
+    /// budget, until none of them fit. The bundle is offered to the user as a whole, and a new
+    /// bundle is drawn if they reject it.
+    fn pick_bundle(
+        &mut self,
+        category: &str,
+        choices: &[config::BundleChoice],
+        eligible: &[bool],
+        budget: f64,
+    ) -> Result<Bundle, PickError<'static>> {
+        let fits = |i: &usize, remaining: f64| choices[*i].cost <= remaining + BUDGET_TOLERANCE;
+        let candidates = (0..choices.len())
+            .filter(|i| eligible[*i] && choices[*i].weight > 0 && fits(i, budget))
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            return Err(PickError::NoBundle(category.to_string()));
+        }
+
+        loop {
+            let mut picked = vec![];
+            let mut remaining = budget;
+            loop {
+                let fitting = candidates
+                    .iter()
+                    .copied()
+                    .filter(|i| !picked.contains(i) && fits(i, remaining))
+                    .collect::<Vec<_>>();
+                let Ok(index) = fitting.choose_weighted(&mut self.rng, |i| choices[*i].weight)
+                else {
+                    break;
+                };
+                remaining -= choices[*index].cost;
+                picked.push(*index);
+            }
+            picked.sort_unstable();
+
+            let bundle = Bundle {
+                choices: picked.iter().map(|i| choices[*i].name.clone()).collect(),
+                cost: picked.iter().map(|i| choices[*i].cost).sum(),
+            };
+
+            if self.ui.call_display_table() {
+                self.display_bundle_table(choices, &candidates, &picked, budget);
+            }
+
+            if self.get_consent(&format_bundle(&bundle)) {
+                return Ok(bundle);
+            }
+        }
+    }
+
+    /// Run the pairs model for the given choices. Only the eligible choices are paired.
+    ///
+    /// If there is an odd number of choices, one of the choices that has sat out the fewest times
+    /// sits out. The rest are paired by repeatedly drawing a pair from the choices that are left,
+    /// weighted by how many rounds it has been since the two were last paired. Pairs that have
+    /// never been paired are weighted as if they were last paired before the first round.
+    fn pick_pairs(
+        &mut self,
+        category: &str,
+        choices: &mut [config::PairChoice],
+        eligible: &[bool],
+        rounds: &mut u64,
+    ) -> Result<Pairs, PickError<'static>> {
+        let people = (0..choices.len())
+            .filter(|i| eligible[*i])
+            .collect::<Vec<_>>();
+        if people.len() < 2 {
+            return Err(PickError::NotEnoughChoices {
+                category: category.to_string(),
+                requested: 2,
+                available: people.len(),
+            });
+        }
+        let round = *rounds + 1;
+        let since = people
+            .iter()
+            .map(|a| {
+                people
+                    .iter()
+                    .map(|b| rounds_since(&choices[*a], &choices[*b].name, round))
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let (pairs, odd, picked) = loop {
+            let mut remaining = (0..people.len()).collect::<Vec<_>>();
+            let odd = if remaining.len() % 2 == 1 {
+                let fewest = remaining
+                    .iter()
+                    .map(|p| choices[people[*p]].sat_out)
+                    .min()
+                    .unwrap();
+                let candidates = remaining
+                    .iter()
+                    .copied()
+                    .filter(|p| choices[people[*p]].sat_out == fewest)
+                    .collect::<Vec<_>>();
+                let odd = *candidates.choose(&mut self.rng).unwrap();
+                remaining.retain(|p| *p != odd);
+                Some(odd)
+            } else {
+                None
+            };
+
+            let mut pairs = vec![];
+            while !remaining.is_empty() {
+                let candidates = remaining
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(i, a)| remaining[i + 1..].iter().map(move |b| (*a, *b)))
+                    .collect::<Vec<_>>();
+                let (a, b) = *candidates
+                    .choose_weighted(&mut self.rng, |(a, b)| since[*a][*b])
+                    .unwrap();
+                remaining.retain(|p| *p != a && *p != b);
+                pairs.push((a, b));
+            }
+            pairs.sort_unstable();
+
+            let names = pairs
+                .iter()
+                .map(|(a, b)| {
+                    (
+                        choices[people[*a]].name.clone(),
+                        choices[people[*b]].name.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let odd_name = odd.map(|o| choices[people[o]].name.clone());
+
+            if self.ui.call_display_table() {
+                let mut partners = vec![None; people.len()];
+                for (a, b) in &pairs {
+                    partners[*a] = Some(*b);
+                    partners[*b] = Some(*a);
+                }
+                self.display_pairs_table(choices, &people, &since, &partners);
+            }
+
+            if self.get_consent(&format_pairs(&names, odd_name.as_deref())) {
+                break (
+                    pairs,
+                    odd,
+                    Pairs {
+                        pairs: names,
+                        sitting_out: odd_name,
+                    },
+                );
+            }
+        };
+
+        *rounds = round;
+        for (a, b) in &pairs {
+            let (a, b) = (people[*a], people[*b]);
+            let name = choices[b].name.clone();
+            choices[a].last_paired.insert(name, round);
+            let name = choices[a].name.clone();
+            choices[b].last_paired.insert(name, round);
+        }
+        if let Some(odd) = odd {
+            choices[people[odd]].sat_out += 1;
+        }
+
+        Ok(picked)
+    }
+
+    /// Run the secret santa model for the given participants. Only the eligible participants take
+    /// part. When the user accepts an assignment, it is added to the end of the history.
+    fn pick_secret_santa(
+        &mut self,
+        category: &str,
+        choices: &[config::SecretSantaChoice],
+        eligible: &[bool],
+        avoid_rounds: usize,
+        history: &mut Vec<BTreeMap<String, String>>,
+    ) -> Result<Vec<(String, String)>, PickError<'static>> {
+        let participants = (0..choices.len())
+            .filter(|i| eligible[*i])
+            .collect::<Vec<_>>();
+        if participants.len() < 2 {
+            return Err(PickError::NotEnoughChoices {
+                category: category.to_string(),
+                requested: 2,
+                available: participants.len(),
+            });
+        }
+        let recent = &history[history.len().saturating_sub(avoid_rounds)..];
+        let allowed = participants
+            .iter()
+            .map(|g| {
+                let giver = &choices[*g];
+                participants
+                    .iter()
+                    .map(|r| {
+                        let recipient = &choices[*r].name;
+                        g != r
+                            && !giver.exclude.contains(recipient)
+                            && !recent
+                                .iter()
+                                .any(|round| round.get(&giver.name) == Some(recipient))
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let assignment = loop {
+            let mut recipients = vec![None; participants.len()];
+            let mut taken = vec![false; participants.len()];
+            if !find_assignment(&allowed, &mut recipients, &mut taken, &mut self.rng) {
+                return Err(PickError::NoAssignment(category.to_string()));
+            }
+            let assignment = participants
+                .iter()
+                .zip(recipients)
+                .map(|(g, r)| {
+                    (
+                        choices[*g].name.clone(),
+                        choices[participants[r.unwrap()]].name.clone(),
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            if self.ui.call_display_table() {
+                self.display_assignment_table(&assignment);
+            }
+
+            if self.get_consent(&format_assignment(&assignment)) {
+                break assignment;
+            }
+        };
+
+        history.push(assignment.iter().cloned().collect());
+
+        Ok(assignment)
+    }
+
+    /// Run the teams model for the given choices, splitting the eligible choices into
+    /// `team_count` teams.
+    ///
+    /// The choices that must stay together are grouped into units, which are dealt out to the
+    /// teams at random by [`random_teams`]. [`balance_teams`] then swaps units between teams to
+    /// balance their total skill.
+    fn pick_teams(
+        &mut self,
+        category: &str,
+        choices: &[config::TeamChoice],
+        eligible: &[bool],
+        team_count: usize,
+        together: &[Vec<String>],
+        apart: &[Vec<String>],
+    ) -> Result<Vec<Vec<String>>, PickError<'static>> {
+        let players = (0..choices.len())
+            .filter(|i| eligible[*i])
+            .collect::<Vec<_>>();
+        if players.len() < team_count {
+            return Err(PickError::NotEnoughChoices {
+                category: category.to_string(),
+                requested: team_count,
+                available: players.len(),
+            });
+        }
+        let position = |name: &String| players.iter().position(|p| &choices[*p].name == name);
+
+        // Each player starts in their own unit, and the units of players that must stay together
+        // are merged.
+        let mut unit_of = (0..players.len()).collect::<Vec<_>>();
+        for group in together {
+            let members = group.iter().filter_map(position).collect::<Vec<_>>();
+            for member in members.iter().skip(1) {
+                let (from, to) = (unit_of[*member], unit_of[members[0]]);
+                unit_of
+                    .iter_mut()
+                    .filter(|u| **u == from)
+                    .for_each(|u| *u = to);
+            }
+        }
+        let mut labels = unit_of.clone();
+        labels.sort_unstable();
+        labels.dedup();
+        let units = labels
+            .iter()
+            .map(|l| {
+                (0..players.len())
+                    .filter(|p| unit_of[*p] == *l)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let unit_index = |player: usize| labels.binary_search(&unit_of[player]).unwrap();
+
+        let mut conflicts = vec![vec![false; units.len()]; units.len()];
+        for group in apart {
+            let members = group.iter().filter_map(position).collect::<Vec<_>>();
+            for (i, a) in members.iter().enumerate() {
+                for b in members[i + 1..].iter() {
+                    let (a, b) = (unit_index(*a), unit_index(*b));
+                    if a == b {
+                        return Err(PickError::NoTeams(category.to_string()));
+                    }
+                    conflicts[a][b] = true;
+                    conflicts[b][a] = true;
+                }
+            }
+        }
+        let sizes = units.iter().map(|u| u.len()).collect::<Vec<_>>();
+        let skills = units
+            .iter()
+            .map(|u| u.iter().map(|p| choices[players[*p]].skill).sum::<f64>())
+            .collect::<Vec<_>>();
+        let capacity = players.len().div_ceil(team_count);
+
+        loop {
+            let Some(mut team_of) = (0..TEAM_ATTEMPTS).find_map(|_| {
+                random_teams(&sizes, &conflicts, capacity, team_count, &mut self.rng)
+            }) else {
+                return Err(PickError::NoTeams(category.to_string()));
+            };
+            balance_teams(
+                &mut team_of,
+                &sizes,
+                &skills,
+                &conflicts,
+                team_count,
+                &mut self.rng,
+            );
+
+            let members = (0..team_count)
+                .map(|t| {
+                    let mut members = (0..players.len())
+                        .filter(|p| team_of[unit_index(*p)] == t)
+                        .collect::<Vec<_>>();
+                    members.sort_unstable();
+                    members
+                })
+                .collect::<Vec<_>>();
+            let teams = members
+                .iter()
+                .map(|m| {
+                    m.iter()
+                        .map(|p| choices[players[*p]].name.clone())
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>();
+
+            if self.ui.call_display_table() {
+                let totals = members
+                    .iter()
+                    .map(|m| m.iter().map(|p| choices[players[*p]].skill).sum())
+                    .collect::<Vec<_>>();
+                self.display_teams_table(&teams, &totals);
+            }
+
+            if self.get_consent(&format_teams(&teams)) {
+                break Ok(teams);
+            }
+        }
+    }
+
+    /// Run the weighted model for the given choices.
+    fn pick_weighted(
+        &mut self,
+        choices: &[config::WeightedChoice],
+        eligible: &[bool],
+        count: usize,
+    ) -> Vec<usize> {
+        let initialize_candidates = |picked: &[usize]| {
+            choices
+                .iter()
+                .enumerate()
+                .filter(|x| eligible[x.0] && x.1.weight > 0 && !picked.contains(&x.0))
+                .map(|x| ((x.0, &x.1.name), x.1.weight))
+                .collect::<Vec<_>>()
+        };
+
+        self.pick_weighted_distinct(count, &initialize_candidates, &[])
+    }
+
+    /// Run [`Engine::pick_weighted_common`] until `count` distinct choices have been accepted.
+    ///
+    /// The initialize_candidates() function is given the indices of the choices that have already
+    /// been accepted, and should not return those choices as candidates. Otherwise, it should
+    /// behave as described in [`Engine::pick_weighted_common`].
+    fn pick_weighted_distinct<W: Weight>(
+        &mut self,
+        count: usize,
+        initialize_candidates: &dyn Fn(&[usize]) -> WeightedCandidates<'a, W>,
+        columns: &[Column],
+    ) -> Vec<usize> {
+        let mut picked = Vec::with_capacity(count);
+
+        while picked.len() < count {
+            let index = self.pick_weighted_common(&|| initialize_candidates(&picked), columns);
+            picked.push(index);
+        }
+
+        picked
+    }
+
+    /// A common weighted choice algorithm used as the core of many models.
+    ///
+    /// The initialize_candidates() function should return a Vector of 2-tuples. The first element
+    /// of the 2-tuple is also a 2-tuple, specifying the original index of the choice and the human
+    /// readable name of the choice. The second element of the outer 2-tuple should express the
+    /// weight of that choice. For example, if the first choice is "ice cream" and has a weight of
+    /// 5, the data structure would look like this: ((0, "ice cream"), 5)
+    ///
+    /// The columns are added to the chance table that is displayed to the user, between the names
+    /// and the weights of the candidates.
+    fn pick_weighted_common<W: Weight>(
+        &mut self,
+        initialize_candidates: &dyn Fn() -> WeightedCandidates<'a, W>,
+        columns: &[Column],
+    ) -> usize {
+        self.pick_weighted_with_feedback(initialize_candidates, columns)
+            .0
+    }
+
+    /// Run [`Engine::pick_weighted_common`], and also return the original indices of the choices
+    /// that the user rejected along the way, in the order they were rejected.
+    fn pick_weighted_with_feedback<W: Weight>(
+        &mut self,
+        initialize_candidates: &dyn Fn() -> WeightedCandidates<'a, W>,
+        columns: &[Column],
+    ) -> (usize, Vec<usize>) {
+        let mut candidates = initialize_candidates();
+        let mut rejected = vec![];
+
+        loop {
+            let (index, choice) = candidates
+                .choose_weighted(&mut self.rng, |item| item.1)
+                .unwrap()
+                .0;
+
+            if self.ui.call_display_table() {
+                self.display_weighted_chance_table(index, &candidates, columns);
+            }
+
+            if self.get_consent(&choice[..]) {
+                break (index, rejected);
+            }
+
+            rejected.push(index);
+            if candidates.len() > 1 {
+                candidates.remove(candidates.iter().position(|x| (x.0).1 == choice).unwrap());
+            } else {
+                self.express_disapproval();
+                candidates = initialize_candidates();
+            }
+        }
+    }
+
+    /// Display the given table to the user, adding a row for each choice that was excluded from
+    /// the pick. The reasons that the choices were excluded are shown in a new Status column.
+    fn display_table<'t>(&'t self, mut table: ui::Table<'t>) {
+        if !self.excluded.is_empty() {
+            table.header.push("Status".into());
+            for row in table.rows.iter_mut() {
+                row.cells.push(ui::Cell::Text(""));
+            }
+            for (name, reason) in self.excluded.iter() {
+                let mut cells = vec![ui::Cell::from(name.as_str())];
+                cells.resize_with(table.header.len() - 1, || ui::Cell::Text(""));
+                cells.push(ui::Cell::from(reason.as_str()));
+                table.rows.push(ui::Row {
+                    cells,
+                    chosen: false,
+                });
+            }
+            if !table.footer.is_empty() {
+                table.footer.push(ui::Cell::Text(""));
+            }
+        }
+
+        self.ui.display_table(&table);
+    }
+
+    /// Print a table to self.output showing every choice in the deck model, whether it is still
+    /// in the deck, and its chance of being chosen.
+    ///
+    /// # Arguments
+    ///
+    /// `index` - The index of the choice that was chosen. This is used to turn the chosen
+    ///     choice yellow in the table.
+    /// `choices` - All of the deck's choices.
+    /// `in_deck` - Whether each of the choices is still in the deck.
+    /// `candidates` - The indices of the choices that could have been chosen.
+    fn display_deck_table(
+        &mut self,
+        index: usize,
+        choices: &[config::DeckChoice],
+        in_deck: &[bool],
+        candidates: &[usize],
+    ) {
+        let header: Vec<ui::Cell> = vec!["Name".into(), "In Deck".into(), "Chance".into()];
+        let mut rows = vec![];
+        for (i, choice) in choices.iter().enumerate() {
+            let chance = if candidates.contains(&i) {
+                100. / (candidates.len() as f64)
+            } else {
+                0.
+            };
+            let cells: Vec<ui::Cell> = vec![
+                ui::Cell::from(choice.name.as_ref()),
+                ui::Cell::Boolean(in_deck[i]),
+                chance.into(),
+            ];
+            rows.push(ui::Row {
+                cells,
+                chosen: i == index,
+            });
+        }
+        let remaining = in_deck.iter().filter(|d| **d).count() as u64;
+        let footer: Vec<ui::Cell> = vec!["Total".into(), remaining.into(), 100.00.into()];
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output showing the candidates in the order they appear in the list,
+    /// along with their chance of being chosen.
+    ///
+    /// # Arguments
+    ///
+    /// `index` - The index of the candidate that was chosen. This is used to turn the chosen
+    ///     candidate yellow in the table.
+    /// `candidates` - A list of the candidates.
+    /// `chances` - The chance of each of the candidates being chosen.
+    /// `mean` - The mean of the distribution.
+    /// `stddev` - The standard deviation of the distribution.
+    fn display_gaussian_chance_table(
+        &mut self,
+        index: usize,
+        candidates: &[&String],
+        chances: &[f64],
+        mean: f64,
+        stddev: f64,
+    ) {
+        let header: Vec<ui::Cell> = vec!["Name".into(), "Std Devs".into(), "Chance".into()];
+        let mut rows = vec![];
+        let mut total_chance = 0.0;
+        for (i, (candidate, chance)) in candidates.iter().zip(chances).enumerate() {
+            let chance = chance * 100.;
+            total_chance += chance;
+            // How many standard deviations the middle of the candidate's slice is from the mean.
+            let deviations = (i as f64 + 0.5 - mean) / stddev;
+            let mut cells: Vec<ui::Cell> = vec![];
+            let chosen = i == index;
+            cells.push(ui::Cell::from(candidate.as_ref()));
+            cells.push(ui::Cell::Decimal(deviations));
+            cells.push(chance.into());
+            let row = ui::Row { cells, chosen };
+            rows.push(row);
+        }
+        let footer: Vec<ui::Cell> = vec!["Total".into(), ui::Cell::Text(""), total_chance.into()];
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output with the costs and weights of the given candidates for a
+    /// bundle, highlighting the ones that were picked, along with the total cost of the bundle
+    /// and the budget.
+    fn display_bundle_table(
+        &mut self,
+        choices: &[config::BundleChoice],
+        candidates: &[usize],
+        picked: &[usize],
+        budget: f64,
+    ) {
+        let rows = candidates
+            .iter()
+            .map(|i| ui::Row {
+                cells: vec![
+                    ui::Cell::from(&choices[*i].name[..]),
+                    ui::Cell::Decimal(choices[*i].cost),
+                    ui::Cell::Unsigned(choices[*i].weight),
+                ],
+                chosen: picked.contains(i),
+            })
+            .collect();
+        let cost = picked.iter().map(|i| choices[*i].cost).sum();
+
+        self.display_table(ui::Table {
+            footer: vec!["Total".into(), ui::Cell::Decimal(cost), ui::Cell::Text("")],
+            header: vec!["Name".into(), "Cost".into(), "Weight".into()],
+            rows,
+        });
+        self.ui.info(&format!(
+            "Budget: {:.2}, left over: {:.2}",
+            budget,
+            budget - cost
+        ));
+    }
+
+    /// Print a table to self.output with the pairing history of the given people, as a matrix of
+    /// how many rounds it has been since each two of them were last paired, along with their
+    /// proposed partners.
+    fn display_pairs_table(
+        &mut self,
+        choices: &[config::PairChoice],
+        people: &[usize],
+        since: &[Vec<u64>],
+        partners: &[Option<usize>],
+    ) {
+        let mut header: Vec<ui::Cell> = vec!["Name".into()];
+        header.extend(
+            people
+                .iter()
+                .map(|p| ui::Cell::from(choices[*p].name.as_str())),
+        );
+        header.extend(["Sat Out".into(), "Partner".into()]);
+        let rows = people
+            .iter()
+            .enumerate()
+            .map(|(a, person)| {
+                let choice = &choices[*person];
+                let mut cells = vec![ui::Cell::from(choice.name.as_str())];
+                cells.extend(people.iter().enumerate().map(|(b, other)| {
+                    if a == b {
+                        ui::Cell::Text("")
+                    } else if choice.last_paired.contains_key(&choices[*other].name) {
+                        ui::Cell::Unsigned(since[a][b])
+                    } else {
+                        ui::Cell::Text("never")
+                    }
+                }));
+                cells.push(ui::Cell::Unsigned(choice.sat_out));
+                cells.push(match partners[a] {
+                    Some(b) => ui::Cell::from(choices[people[b]].name.as_str()),
+                    None => ui::Cell::Text("sits out"),
+                });
+                ui::Row {
+                    cells,
+                    chosen: false,
+                }
+            })
+            .collect();
+        let footer = vec![];
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output showing the chance of each number, or group of numbers, that
+    /// the dice and range models can pick. The chances are given as fractions of 1.
+    fn display_distribution_table(&mut self, rows: &[(String, f64)], chosen: usize) {
+        let header: Vec<ui::Cell> = vec!["Value".into(), "Chance".into()];
+        let rows = rows
+            .iter()
+            .enumerate()
+            .map(|(i, (label, chance))| ui::Row {
+                cells: vec![ui::Cell::from(label.as_str()), (chance * 100.0).into()],
+                chosen: i == chosen,
+            })
+            .collect();
+        let footer: Vec<ui::Cell> = vec!["Total".into(), 100.00.into()];
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output showing who each participant gives a gift to.
+    fn display_assignment_table(&mut self, assignment: &[(String, String)]) {
+        let header: Vec<ui::Cell> = vec!["Giver".into(), "Recipient".into()];
+        let rows = assignment
+            .iter()
+            .map(|(giver, recipient)| ui::Row {
+                cells: vec![
+                    ui::Cell::from(giver.as_str()),
+                    ui::Cell::from(recipient.as_str()),
+                ],
+                chosen: false,
+            })
+            .collect();
+        let footer = vec![];
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output with a column for each of the given teams, listing its
+    /// members, and a footer with each team's total skill.
+    fn display_teams_table(&mut self, teams: &[Vec<String>], totals: &[f64]) {
+        let names = (1..=teams.len())
+            .map(|t| format!("Team {}", t))
+            .collect::<Vec<_>>();
+        let header = names
+            .iter()
+            .map(|n| ui::Cell::from(n.as_str()))
+            .collect::<Vec<_>>();
+        let longest = teams.iter().map(|t| t.len()).max().unwrap_or(0);
+        let rows = (0..longest)
+            .map(|i| ui::Row {
+                cells: teams
+                    .iter()
+                    .map(|t| ui::Cell::from(t.get(i).map_or("", |m| m.as_str())))
+                    .collect(),
+                chosen: false,
+            })
+            .collect();
+        let footer = totals.iter().map(|t| ui::Cell::Decimal(*t)).collect();
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output showing the ratings of the given choices, sorted by rating.
+    fn display_ratings_table(&mut self, choices: &[config::RatedChoice]) {
+        let mut choices = choices.iter().collect::<Vec<_>>();
+        choices.sort_by(|a, b| b.rating.total_cmp(&a.rating));
+
+        let header: Vec<ui::Cell> = vec!["Name".into(), "Rating".into(), "Comparisons".into()];
+        let mut rows = vec![];
+        for choice in choices {
+            let cells: Vec<ui::Cell> = vec![
+                ui::Cell::from(choice.name.as_ref()),
+                ui::Cell::Decimal(choice.rating),
+                ui::Cell::Unsigned(choice.comparisons),
+            ];
+            rows.push(ui::Row {
+                cells,
+                chosen: false,
+            });
+        }
+        let footer = vec![];
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output showing the candidates, sorted by chance of being chosen.
+    ///
+    /// # Arguments
+    ///
+    /// `candidates` - A list of the candidates that have not yet been rejected, starting with the
+    ///     candidate that was chosen. The chosen candidate will be turned yellow in the table.
+    fn display_lru_table(&mut self, candidates: &[&String]) {
+        let header: Vec<ui::Cell> = vec!["Name".into()];
+        let mut rows = vec![];
+        for (i, candidate) in candidates.iter().rev().enumerate() {
+            let mut cells: Vec<ui::Cell> = vec![];
+            let chosen = i == candidates.len() - 1;
+            cells.push(ui::Cell::from(candidate.as_ref()));
+            rows.push(ui::Row { cells, chosen });
+        }
+        let footer = vec![];
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output showing the candidates of the quota model, sorted by their
+    /// deficits.
+    ///
+    /// # Arguments
+    ///
+    /// `index` - The index of the choice that was chosen. This is used to turn the chosen
+    ///     choice yellow in the table.
+    /// `choices` - All of the quota model's choices.
+    /// `picks` - How many times each of the choices has been picked.
+    /// `deficits` - How many picks each of the choices is behind its target share.
+    /// `candidates` - The indices of the choices that could have been chosen, sorted by deficit.
+    /// `tied` - How many of the first candidates were tied, and so had a chance of being chosen.
+    fn display_quota_table(
+        &mut self,
+        index: usize,
+        choices: &[config::QuotaChoice],
+        picks: &[u64],
+        deficits: &[f64],
+        candidates: &[usize],
+        tied: usize,
+    ) {
+        let total_target = choices.iter().map(|c| c.target).sum::<u64>().max(1) as f64;
+        let total_picks = picks.iter().sum::<u64>().max(1) as f64;
+
+        let header: Vec<ui::Cell> = vec![
+            "Name".into(),
+            "Target".into(),
+            "Actual".into(),
+            "Deficit".into(),
+            "Chance".into(),
+        ];
+        let mut rows = vec![];
+        for (position, i) in candidates.iter().enumerate() {
+            let chance = if position < tied {
+                100. / (tied as f64)
+            } else {
+                0.
+            };
+            let cells: Vec<ui::Cell> = vec![
+                ui::Cell::from(choices[*i].name.as_ref()),
+                (choices[*i].target as f64 / total_target * 100.).into(),
+                (picks[*i] as f64 / total_picks * 100.).into(),
+                ui::Cell::Decimal(deficits[*i]),
+                chance.into(),
+            ];
+            rows.push(ui::Row {
+                cells,
+                chosen: *i == index,
+            });
+        }
+        let footer: Vec<ui::Cell> = vec![
+            "Total".into(),
+            ui::Cell::Text(""),
+            ui::Cell::Text(""),
+            ui::Cell::Text(""),
+            100.00.into(),
+        ];
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+
+    /// Print a table to self.output showing the candidates, sorted by chance of being chosen.
+    ///
+    /// # Arguments
+    ///
+    /// `index` - The index of the candidate that was chosen. This is used to turn the chosen
+    ///     candidate yellow in the table.
+    /// `candidates` - A list of the candidates.
+    fn display_weighted_chance_table<W: Weight>(
+        &mut self,
+        index: usize,
+        candidates: &[((usize, &'a String), W)],
+        columns: &[Column],
+    ) {
+        // Let's make a copy of the candidate list so that we can sort it for the table
+        // without sorting the real candidate list.
+        let mut candidates = candidates.to_owned();
+        candidates.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+
+        let total: W = candidates.iter().map(|x| x.1).sum();
+
+        let mut rows = vec![];
+        let mut header: Vec<ui::Cell> = vec!["Name".into()];
+        header.extend(columns.iter().map(|c| ui::Cell::from(c.0)));
+        header.extend(["Weight".into(), "Chance".into()]);
+        for candidate in candidates.iter() {
+            let chance: f64 = candidate.1.to_f64() / total.to_f64() * 100.;
+            let mut cells: Vec<ui::Cell> = vec![];
+            let chosen = (candidate.0).0 == index;
+            cells.push(ui::Cell::from((candidate.0).1.as_ref()));
+            cells.extend(columns.iter().map(|c| c.1((candidate.0).0)));
+            cells.push(candidate.1.to_cell());
+            cells.push(chance.into());
+            rows.push(ui::Row { cells, chosen });
+        }
+        let mut footer: Vec<ui::Cell> = vec!["Total".into()];
+        footer.extend(columns.iter().map(|_| ui::Cell::Text("")));
+        footer.extend([total.to_cell(), 100.00.into()]);
+
+        self.display_table(ui::Table {
+            footer,
+            header,
+            rows,
+        });
+    }
+}
+
+/// Find a random assignment of recipients to givers, where `allowed[g][r]` says whether giver
+/// `g` may give to recipient `r`. Every recipient must be assigned to exactly one giver.
+///
+/// This is a backtracking search that always assigns the giver with the fewest remaining options
+/// next, trying its options in a random order. `recipients` and `taken` hold the partial
+/// assignment, and are left holding the full assignment if one is found.
+///
+/// # Returns
+///
+/// Whether an assignment was found.
+fn find_assignment<R: Rng + ?Sized>(
+    allowed: &[Vec<bool>],
+    recipients: &mut [Option<usize>],
+    taken: &mut [bool],
+    rng: &mut R,
+) -> bool {
+    let options = |giver: usize, taken: &[bool]| {
+        (0..taken.len())
+            .filter(|r| allowed[giver][*r] && !taken[*r])
+            .collect::<Vec<_>>()
+    };
+    let Some(giver) = (0..recipients.len())
+        .filter(|g| recipients[*g].is_none())
+        .min_by_key(|g| options(*g, taken).len())
+    else {
+        return true;
+    };
+
+    let mut options = options(giver, taken);
+    options.shuffle(rng);
+    for recipient in options {
+        recipients[giver] = Some(recipient);
+        taken[recipient] = true;
+        if find_assignment(allowed, recipients, taken, rng) {
+            return true;
+        }
+        recipients[giver] = None;
+        taken[recipient] = false;
+    }
+
+    false
+}
+
+/// Return how many teams the eligible choices of a teams category should be split into. If
+/// `teams` isn't set, there are as many teams of `team_size` as the choices need, and if neither
+/// is set, there are 2 teams.
+fn team_count(eligible: &[bool], teams: Option<usize>, team_size: Option<usize>) -> usize {
+    let players = eligible.iter().filter(|e| **e).count();
+
+    match (teams, team_size) {
+        (Some(teams), _) => teams,
+        (None, Some(team_size)) => players.div_ceil(team_size.max(1)),
+        (None, None) => 2,
+    }
+    .max(1)
+}
+
+/// Deal the given units out to `team_count` teams at random, so that no team holds more than
+/// `capacity` players and no team holds two units that conflict with each other. The largest
+/// units are dealt first, each to one of the smallest teams that it fits on.
+///
+/// # Returns
+///
+/// The team of each unit, or `None` if a unit didn't fit on any team.
+fn random_teams<R: Rng + ?Sized>(
+    sizes: &[usize],
+    conflicts: &[Vec<bool>],
+    capacity: usize,
+    team_count: usize,
+    rng: &mut R,
+) -> Option<Vec<usize>> {
+    let mut order = (0..sizes.len()).collect::<Vec<_>>();
+    order.shuffle(rng);
+    order.sort_by_key(|u| std::cmp::Reverse(sizes[*u]));
+    let mut team_of = vec![usize::MAX; sizes.len()];
+    let mut team_sizes = vec![0; team_count];
+
+    for unit in order {
+        let mut teams = (0..team_count).collect::<Vec<_>>();
+        teams.shuffle(rng);
+        teams.sort_by_key(|t| team_sizes[*t]);
+        let team = teams.into_iter().find(|t| {
+            team_sizes[*t] + sizes[unit] <= capacity
+                && !(0..sizes.len()).any(|u| team_of[u] == *t && conflicts[unit][u])
+        })?;
+        team_of[unit] = team;
+        team_sizes[team] += sizes[unit];
+    }
+
+    Some(team_of)
+}
+
+/// Swap units of the same size between teams in a random order, as long as each swap brings the
+/// teams' total skills closer together and doesn't put conflicting units on the same team. This
+/// stops once no swap helps.
+fn balance_teams<R: Rng + ?Sized>(
+    team_of: &mut [usize],
+    sizes: &[usize],
+    skills: &[f64],
+    conflicts: &[Vec<bool>],
+    team_count: usize,
+    rng: &mut R,
+) {
+    // The sum of the squares of the teams' total skills is smallest when the totals are even.
+    let spread = |team_of: &[usize]| {
+        let mut totals = vec![0.0; team_count];
+        for (unit, team) in team_of.iter().enumerate() {
+            totals[*team] += skills[unit];
+        }
+        totals.iter().map(|t| t * t).sum::<f64>()
+    };
+    let fits = |team_of: &[usize], unit: usize| {
+        !(0..team_of.len()).any(|u| team_of[u] == team_of[unit] && conflicts[unit][u])
+    };
+
+    loop {
+        let current = spread(team_of);
+        let mut swaps = (0..sizes.len())
+            .flat_map(|a| (a + 1..sizes.len()).map(move |b| (a, b)))
+            .filter(|(a, b)| team_of[*a] != team_of[*b] && sizes[*a] == sizes[*b])
+            .collect::<Vec<_>>();
+        swaps.shuffle(rng);
+
+        let improved = swaps.into_iter().any(|(a, b)| {
+            team_of.swap(a, b);
+            if fits(team_of, a) && fits(team_of, b) && spread(team_of) < current - 1e-9 {
+                return true;
+            }
+            team_of.swap(a, b);
+            false
+        });
+        if !improved {
+            break;
+        }
+    }
+}
+
+/// Express the given bundle as a single line, such as `chips, soda (total cost 5.50)`.
+fn format_bundle(bundle: &Bundle) -> String {
+    format!(
+        "{} (total cost {:.2})",
+        bundle.choices.join(", "),
+        bundle.cost
+    )
+}
+
+/// Express the picks of the groups of a stratified category as a single line, such as
+/// `protein: tofu; starch: rice; vegetable: broccoli`.
+fn format_groups(picks: &[(String, String)]) -> String {
+    picks
+        .iter()
+        .map(|(group, pick)| format!("{}: {}", group, pick))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Express the given teams as a single line, such as `Team 1: Alice, Bob; Team 2: Carol, Dave`.
+fn format_teams(teams: &[Vec<String>]) -> String {
+    teams
+        .iter()
+        .enumerate()
+        .map(|(i, members)| format!("Team {}: {}", i + 1, members.join(", ")))
+        .collect::<Vec<_>>()
+        .join("; ")
+}
+
+/// Parse the given dice roll, such as `2d6+1`, into a list of dice and the sum of its whole numbers.
+/// Each die is given as the number of dice, which is negative for dice that are subtracted, and
+/// the number of sides.
+///
+/// Returns `None` if the roll can't be parsed, has more than [`MAX_DICE`] dice or dice with more
+/// than [`MAX_SIDES`] sides, or can come to a total that doesn't fit in an `i64`.
+fn parse_dice(roll: &str) -> Option<(Vec<(i64, u64)>, i64)> {
+    let roll = roll
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_lowercase();
+    let mut terms = vec![];
+    let mut start = 0;
+    for (i, c) in roll.char_indices() {
+        if (c == '+' || c == '-') && i > 0 {
+            terms.push(&roll[start..i]);
+            start = i;
+        }
+    }
+    terms.push(&roll[start..]);
+
+    let mut dice = vec![];
+    let mut modifier: i64 = 0;
+    let mut total: u64 = 0;
+    for term in terms {
+        let (sign, term) = match term.strip_prefix('-') {
+            Some(term) => (-1, term),
+            None => (1, term.strip_prefix('+').unwrap_or(term)),
+        };
+        match term.split_once('d') {
+            Some((count, sides)) => {
+                let count: u64 = if count.is_empty() {
+                    1
+                } else {
+                    count.parse().ok()?
+                };
+                let sides: u64 = sides.parse().ok()?;
+                total = total.saturating_add(count);
+                if count == 0 || total > MAX_DICE || sides == 0 || sides > MAX_SIDES {
+                    return None;
+                }
+                dice.push((sign * count as i64, sides));
+            }
+            None => {
+                let number: i64 = term.parse().ok()?;
+                modifier = modifier.checked_add(sign * number)?;
+            }
+        }
+    }
+    // The dice can move the total at most this far from the modifier, and every total that they
+    // can come to must fit, so that rolling them can't overflow.
+    let spread = dice
+        .iter()
+        .map(|(count, sides)| count.unsigned_abs() * sides)
+        .sum::<u64>() as i64;
+    modifier.checked_add(spread)?;
+    modifier.checked_sub(spread)?;
+
+    Some((dice, modifier))
+}
+
+/// Return the lowest total that the given dice roll can come to, and the chance of each total
+/// from there up to the highest total.
+fn dice_chances(dice: &[(i64, u64)], modifier: i64) -> (i64, Vec<f64>) {
+    let mut lowest = modifier;
+    let mut chances = vec![1.0];
+    for (count, sides) in dice.iter() {
+        let sides = *sides as usize;
+        for _ in 0..count.unsigned_abs() {
+            // Each new total is the average of the chances of the totals that one more die can
+            // reach it from, which we add up with a running sum.
+            let mut next = vec![0.0; chances.len() + sides - 1];
+            let mut sum = 0.0;
+            for (i, n) in next.iter_mut().enumerate() {
+                if i < chances.len() {
+                    sum += chances[i];
+                }
+                if i >= sides {
+                    sum -= chances[i - sides];
+                }
+                *n = sum / sides as f64;
+            }
+            chances = next;
+            lowest += if *count < 0 { -(sides as i64) } else { 1 };
+        }
+    }
+
+    (lowest, chances)
+}
+
+/// Return how many decimal places the given step is written with, so that the numbers it steps
+/// through can be written with the same precision.
+fn step_decimals(step: f64) -> usize {
+    step.to_string()
+        .split_once('.')
+        .map_or(0, |(_, decimals)| decimals.len())
+}
+
+/// Express a span of numbers as a single label, such as `3 – 5`, or `3` if it only holds one.
+fn span_label(first: String, last: String) -> String {
+    if first == last {
+        first
+    } else {
+        format!("{} – {}", first, last)
+    }
+}
+
+/// A choice that a roll on a loot table can land on, as found by [`loot_outcomes`].
+struct LootOutcome<'c> {
+    /// The name of the choice.
+    name: &'c String,
+    /// The names of the choices whose tables lead to the choice, or an empty string if the choice
+    /// is one of the category's own choices.
+    path: String,
+    /// The table that the choice is in.
+    table: &'c [config::LootChoice],
+    /// The chance that a roll lands on the choice.
+    chance: f64,
+    /// How many more times the choice rolls its table.
+    reroll: u64,
+}
+
+/// Find every item and rerolling choice that a roll on the given loot table can land on, following
+/// the tables of the choices that have them, and add them to `outcomes`.
+///
+/// # Arguments
+///
+/// * `table` - The table to roll on.
+/// * `eligible` - Which of the table's choices can be rolled, or `None` if they all can.
+/// * `rerolls` - Whether the table's rerolling choices can be rolled. The tables of its choices
+///   can always be rerolled.
+/// * `chance` - The chance of rolling on the table at all.
+/// * `path` - The names of the choices whose tables lead to the table, joined by arrows.
+/// * `outcomes` - The list to add the outcomes to.
+fn loot_outcomes<'c>(
+    table: &'c [config::LootChoice],
+    eligible: Option<&[bool]>,
+    rerolls: bool,
+    chance: f64,
+    path: &str,
+    outcomes: &mut Vec<LootOutcome<'c>>,
+) {
+    let rollable = table
+        .iter()
+        .enumerate()
+        .filter(|(i, c)| {
+            c.weight > 0 && eligible.is_none_or(|e| e[*i]) && (rerolls || c.reroll == 0)
+        })
+        .map(|(_, c)| c)
+        .collect::<Vec<_>>();
+    let total = rollable.iter().map(|c| c.weight).sum::<u64>() as f64;
+
+    for choice in rollable {
+        let chance = chance * choice.weight as f64 / total;
+        if choice.reroll == 0 && !choice.table.is_empty() {
+            let path = if path.is_empty() {
+                choice.name.clone()
+            } else {
+                format!("{} → {}", path, choice.name)
+            };
+            loot_outcomes(&choice.table, None, true, chance, &path, outcomes);
+        } else {
+            outcomes.push(LootOutcome {
+                name: &choice.name,
+                path: path.to_string(),
+                table,
+                chance,
+                reroll: choice.reroll,
+            });
+        }
+    }
+}
+
+/// Return the most times that any of the choices in the given loot table, or in the tables of its
+/// choices, rerolls its table.
+fn max_reroll(table: &[config::LootChoice]) -> u64 {
+    table
+        .iter()
+        .map(|c| c.reroll.max(max_reroll(&c.table)))
+        .max()
+        .unwrap_or(0)
+}
+
+/// Express the given pairs as a single line, such as `Alice & Bob, Carol & Dave; Erin sits out`.
+fn format_pairs(pairs: &[(String, String)], odd: Option<&str>) -> String {
+    let pairs = pairs
+        .iter()
+        .map(|(a, b)| format!("{} & {}", a, b))
+        .collect::<Vec<_>>()
+        .join(", ");
+    match odd {
+        Some(odd) => format!("{}; {} sits out", pairs, odd),
+        None => pairs,
+    }
+}
+
+/// Return how many rounds it has been since the given choice was last paired with the choice
+/// called `other`, counting the given `round` as the current round. Choices that have never been
+/// paired count as having been paired in round 0.
+fn rounds_since(choice: &config::PairChoice, other: &str, round: u64) -> u64 {
+    round
+        - choice
+            .last_paired
+            .get(other)
+            .copied()
+            .unwrap_or(0)
+            .min(round - 1)
+}
+
+/// Express an assignment of givers to recipients as a single line, such as `Alice → Bob, Bob →
+/// Alice`.
+fn format_assignment(assignment: &[(String, String)]) -> String {
+    assignment
+        .iter()
+        .map(|(giver, recipient)| format!("{} → {}", giver, recipient))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Put the given candidates in a random order, by picking each position in turn from the
+/// candidates that are left with the given weights. Candidates with a weight of 0 go at the end, in
+/// the order they are given.
+fn weighted_order<R: Rng + ?Sized>(
+    mut candidates: Vec<usize>,
+    mut weights: Vec<u64>,
+    rng: &mut R,
+) -> Vec<usize> {
+    let mut order = Vec::with_capacity(candidates.len());
+    while weights.iter().any(|w| *w > 0) {
+        let index = WeightedIndex::new(&weights).unwrap().sample(rng);
+        order.push(candidates.remove(index));
+        weights.remove(index);
+    }
+    order.extend(candidates);
+
+    order
+}
+
+/// Update the given category as if the choices at the indices in `order` had been accepted one at
+/// a time, in order, and return the indices of the choices after the update.
+fn update_ordered(
+    category: &mut config::ConfigCategory,
+    eligible: &[bool],
+    order: &[usize],
+) -> Vec<usize> {
+    match category {
+        config::ConfigCategory::Gaussian { choices, .. }
+        | config::ConfigCategory::Lru { choices, .. } => {
+            move_to_end(choices, order);
+            (choices.len() - order.len()..choices.len()).collect()
+        }
+        config::ConfigCategory::Inventory { choices, .. } => {
+            for index in order.iter() {
+                choices[*index].tickets = choices[*index].tickets.saturating_sub(1);
+            }
+            order.to_vec()
+        }
+        config::ConfigCategory::Lottery { choices, .. } => {
+            for index in order.iter() {
+                for (choice, _) in choices.iter_mut().zip(eligible).filter(|(_, e)| **e) {
+                    choice.tickets += choice.weight;
+                }
+                choices[*index].tickets = choices[*index].reset;
+            }
+            order.to_vec()
+        }
+        _ => order.to_vec(),
+    }
+}
+
+/// Move the choices at the given indices to the end of the list, in the order they are given.
+fn move_to_end<T>(choices: &mut Vec<T>, indices: &[usize]) {
+    let mut sorted = indices.to_vec();
+    sorted.sort_unstable();
+
+    // Remove the highest indices first so that the lower indices stay valid while we go.
+    let mut moved: Vec<(usize, T)> = sorted
+        .iter()
+        .rev()
+        .map(|i| (*i, choices.remove(*i)))
+        .collect();
+
+    for index in indices {
+        let position = moved.iter().position(|m| m.0 == *index).unwrap();
+        choices.push(moved.remove(position).1);
+    }
+}
+
+/// Return how many points the winner of a comparison takes from the loser, given their Elo ratings
+/// before the comparison.
+fn elo_change(winner: f64, loser: f64) -> f64 {
+    let expected = 1.0 / (1.0 + 10f64.powf((loser - winner) / 400.0));
+    ELO_K_FACTOR * (1.0 - expected)
+}
+
+/// Return the weight for a choice in the spaced repetition model that has been due for
+/// `days_overdue` days. Choices that are due have a weight of at least 1, which grows by 1 for each
+/// day that they are overdue, while choices that aren't due yet have a weight below 1, which
+/// shrinks the further away they are.
+fn spaced_repetition_weight(days_overdue: f64) -> f64 {
+    if days_overdue >= 0.0 {
+        1.0 + days_overdue
+    } else {
+        1.0 / (1.0 - days_overdue)
+    }
+}
+
+/// Reschedule the given spaced repetition choice after the user recalled it with the given grade
+/// at the given time, using the SM-2 algorithm.
+///
+/// A grade of 3 or more counts as correct recall. The first two correct reviews in a row schedule
+/// the choice 1 and then 6 days ahead, and later ones multiply the interval by the ease. Lower
+/// grades start the repetitions over, scheduling the choice 1 day ahead. Either way, the ease is
+/// adjusted by the grade.
+fn review(choice: &mut config::SpacedRepetitionChoice, grade: u8, now: DateTime<FixedOffset>) {
+    let grade = grade.min(5);
+
+    if grade >= 3 {
+        choice.interval = match choice.repetitions {
+            0 => 1,
+            1 => 6,
+            _ => (choice.interval as f64 * choice.ease).round() as u64,
+        };
+        choice.repetitions += 1;
+    } else {
+        choice.repetitions = 0;
+        choice.interval = 1;
+    }
+
+    let miss = f64::from(5 - grade);
+    choice.ease = (choice.ease + 0.1 - miss * (0.08 + miss * 0.02)).max(MIN_EASE);
+    choice.due = Some(now + chrono::Duration::days(choice.interval as i64));
+}
+
+/// Return whether the parameters of the given recency curve give every choice a weight that is
+/// larger than 0.
+fn recency_curve_usable(curve: config::RecencyCurve) -> bool {
+    match curve {
+        config::RecencyCurve::Linear => true,
+        config::RecencyCurve::Exponential { doubling_days } => {
+            doubling_days.is_finite() && doubling_days > 0.0
+        }
+        config::RecencyCurve::Capped { max_days } => max_days.is_finite() && max_days >= 0.0,
+    }
+}
+
+/// Return the weight for a choice in the recency model that was last picked `days` days ago.
+fn recency_weight(curve: config::RecencyCurve, days: f64) -> f64 {
+    match curve {
+        config::RecencyCurve::Linear => 1.0 + days,
+        config::RecencyCurve::Exponential { doubling_days } => (days / doubling_days).exp2(),
+        config::RecencyCurve::Capped { max_days } => 1.0 + days.min(max_days),
+    }
+}
+
+/// Return the value of the given factor for each of the given choices of a composite category.
+///
+/// The lru factor decays with a choice's position among the choices that are marked in
+/// `eligible`, so that the choices that can't be picked right now don't push the others back.
+fn composite_factor(
+    choices: &[config::CompositeChoice],
+    eligible: &[bool],
+    factor: &config::CompositeFactor,
+) -> Vec<f64> {
+    choices
+        .iter()
+        .enumerate()
+        .map(|(i, c)| match factor {
+            config::CompositeFactor::Weight => c.weight as f64,
+            config::CompositeFactor::Lru { decay } => {
+                let position = eligible[..i].iter().filter(|e| **e).count();
+                decay.powi(position as i32)
+            }
+            config::CompositeFactor::Lottery { .. } => c.tickets as f64,
+            config::CompositeFactor::Tags { boosts } => c
+                .options
+                .tags
+                .iter()
+                .filter_map(|t| boosts.get(t))
+                .product(),
+        })
+        .collect()
+}
+
+/// Return the label of the given factor, for the composite model's chance table.
+fn composite_factor_label(factor: &config::CompositeFactor) -> &'static str {
+    match factor {
+        config::CompositeFactor::Weight => "Static Weight",
+        config::CompositeFactor::Lru { .. } => "LRU Decay",
+        config::CompositeFactor::Lottery { .. } => "Tickets",
+        config::CompositeFactor::Tags { .. } => "Tag Boost",
+    }
+}
+
+/// Return the chance of picking each of the first `len` positions of a list from a
+/// [Gaussian distribution](https://en.wikipedia.org/wiki/Normal_distribution) with the given mean
+/// and standard deviation.
+///
+/// Each position covers one unit of the number line, so position `k` gets the chance of the
+/// distribution landing between `k` and `k + 1`. The chances are then divided by the chance of
+/// landing anywhere in the list, so that they add up to 1. With a mean of 0, this is the same as
+/// folding the distribution in half at the beginning of the list. The mean must be within the
+/// list, so that the list can't miss the whole distribution.
+fn gaussian_chances(len: usize, mean: f64, stddev: f64) -> Vec<f64> {
+    let distribution = statrs::distribution::Normal::new(mean, stddev).unwrap();
+    let total = distribution.cdf(len as f64) - distribution.cdf(0.0);
+
+    (0..len)
+        .map(|k| (distribution.cdf((k + 1) as f64) - distribution.cdf(k as f64)) / total)
+        .collect()
+}
+
+/// Return the standard deviation that gives the last of `len` positions the given chance of
+/// being picked with [`gaussian_chances`], or the closest that it can get to that chance.
+fn gaussian_stddev(len: usize, mean: f64, probability: f64) -> f64 {
+    let error =
+        |log_stddev: f64| gaussian_chances(len, mean, log_stddev.exp())[len - 1] - probability;
+    // Search over the logarithm of the standard deviation, between a tiny fraction of the list
+    // and many times its length, so that the search covers every reasonable spread.
+    let (mut low, mut high) = (((len as f64) / 1_000.0).ln(), ((len as f64) * 1_000.0).ln());
+    let (low_error, high_error) = (error(low), error(high));
+    if low_error.signum() == high_error.signum() {
+        let closest = if low_error.abs() < high_error.abs() {
+            low
+        } else {
+            high
+        };
+        return closest.exp();
+    }
+
+    for _ in 0..GAUSSIAN_SEARCH_ROUNDS {
+        let middle = (low + high) / 2.0;
+        if error(middle).signum() == low_error.signum() {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+
+    ((low + high) / 2.0).exp()
+}
+
+/// Return the weight of each of the given choices of a composite category, by combining the
+/// values of the given factors, as found by [`composite_factor`] for the choices that are marked
+/// in `eligible`. If there are no factors, every choice has a weight of 1.
+fn composite_weights(
+    choices: &[config::CompositeChoice],
+    eligible: &[bool],
+    factors: &[config::CompositeFactor],
+    combine: config::Combine,
+) -> Vec<f64> {
+    let values = factors
+        .iter()
+        .map(|f| composite_factor(choices, eligible, f))
+        .collect::<Vec<_>>();
+
+    (0..choices.len())
+        .map(|i| {
+            let factors = values.iter().map(|v| v[i]);
+            match combine {
+                _ if values.is_empty() => 1.0,
+                config::Combine::Add => factors.sum(),
+                config::Combine::Multiply => factors.product(),
+            }
+        })
+        .collect()
+}
+
+/// Find the indices of the given category's choices that have one of the given `names`.
+fn choice_indices(category: &config::ConfigCategory, names: &[String]) -> Vec<usize> {
+    category_choices(category)
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| names.iter().any(|n| n == c.name()))
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Return the choices of the given category. The choices of a stratified category are the choices
+/// of all of its groups.
+fn category_choices(category: &config::ConfigCategory) -> Vec<&dyn config::ConfigChoice> {
+    fn erase<C: config::ConfigChoice>(choices: &[C]) -> Vec<&dyn config::ConfigChoice> {
+        choices
+            .iter()
+            .map(|c| c as &dyn config::ConfigChoice)
+            .collect()
+    }
+
+    match category {
+        config::ConfigCategory::Bandit { choices, .. } => erase(choices),
+        config::ConfigCategory::Bundle { choices, .. } => erase(choices),
+        config::ConfigCategory::Composite { choices, .. } => erase(choices),
+        config::ConfigCategory::Deck { choices, .. } => erase(choices),
+        config::ConfigCategory::Dice { .. }
+        | config::ConfigCategory::FloatRange { .. }
+        | config::ConfigCategory::IntegerRange { .. } => vec![],
+        config::ConfigCategory::Even { choices, .. }
+        | config::ConfigCategory::Gaussian { choices, .. }
+        | config::ConfigCategory::Geometric { choices, .. }
+        | config::ConfigCategory::Linear { choices, .. }
+        | config::ConfigCategory::Lru { choices, .. }
+        | config::ConfigCategory::Zipf { choices, .. } => erase(choices),
+        config::ConfigCategory::Inventory { choices, .. } => erase(choices),
+        config::ConfigCategory::Lfu { choices, .. } => erase(choices),
+        config::ConfigCategory::Loot { choices, .. } => erase(choices),
+        config::ConfigCategory::Lottery { choices, .. } => erase(choices),
+        config::ConfigCategory::Markov { choices, .. } => erase(choices),
+        config::ConfigCategory::Pairs { choices, .. } => erase(choices),
+        config::ConfigCategory::Quota { choices, .. } => erase(choices),
+        config::ConfigCategory::Rated { choices, .. } => erase(choices),
+        config::ConfigCategory::Recency { choices, .. } => erase(choices),
+        config::ConfigCategory::SecretSanta { choices, .. } => erase(choices),
+        config::ConfigCategory::SpacedRepetition { choices, .. } => erase(choices),
+        config::ConfigCategory::Stratified { groups } => groups
+            .iter()
+            .flat_map(|g| category_choices(&g.category))
+            .collect(),
+        config::ConfigCategory::Teams { choices, .. } => erase(choices),
+        config::ConfigCategory::Weighted { choices, .. } => erase(choices),
+    }
+}
+
+/// Return the choices of the given category, so that they can be modified. The choices of a
+/// stratified category are the choices of all of its groups.
+fn category_choices_mut(
+    category: &mut config::ConfigCategory,
+) -> Vec<&mut dyn config::ConfigChoice> {
+    fn erase<C: config::ConfigChoice>(choices: &mut [C]) -> Vec<&mut dyn config::ConfigChoice> {
+        choices
+            .iter_mut()
+            .map(|c| c as &mut dyn config::ConfigChoice)
+            .collect()
+    }
+
+    match category {
+        config::ConfigCategory::Bandit { choices, .. } => erase(choices),
+        config::ConfigCategory::Bundle { choices, .. } => erase(choices),
+        config::ConfigCategory::Composite { choices, .. } => erase(choices),
+        config::ConfigCategory::Deck { choices, .. } => erase(choices),
+        config::ConfigCategory::Dice { .. }
+        | config::ConfigCategory::FloatRange { .. }
+        | config::ConfigCategory::IntegerRange { .. } => vec![],
+        config::ConfigCategory::Even { choices, .. }
+        | config::ConfigCategory::Gaussian { choices, .. }
+        | config::ConfigCategory::Geometric { choices, .. }
+        | config::ConfigCategory::Linear { choices, .. }
+        | config::ConfigCategory::Lru { choices, .. }
+        | config::ConfigCategory::Zipf { choices, .. } => erase(choices),
+        config::ConfigCategory::Inventory { choices, .. } => erase(choices),
+        config::ConfigCategory::Lfu { choices, .. } => erase(choices),
+        config::ConfigCategory::Loot { choices, .. } => erase(choices),
+        config::ConfigCategory::Lottery { choices, .. } => erase(choices),
+        config::ConfigCategory::Markov { choices, .. } => erase(choices),
+        config::ConfigCategory::Pairs { choices, .. } => erase(choices),
+        config::ConfigCategory::Quota { choices, .. } => erase(choices),
+        config::ConfigCategory::Rated { choices, .. } => erase(choices),
+        config::ConfigCategory::Recency { choices, .. } => erase(choices),
+        config::ConfigCategory::SecretSanta { choices, .. } => erase(choices),
+        config::ConfigCategory::SpacedRepetition { choices, .. } => erase(choices),
+        config::ConfigCategory::Stratified { groups } => groups
+            .iter_mut()
+            .flat_map(|g| category_choices_mut(&mut g.category))
+            .collect(),
+        config::ConfigCategory::Teams { choices, .. } => erase(choices),
+        config::ConfigCategory::Weighted { choices, .. } => erase(choices),
+    }
+}
+
+/// Return the cooldown that the given category applies to choices that don't set their own.
+fn category_cooldown(category: &config::ConfigCategory) -> Option<config::Cooldown> {
+    match category {
+        config::ConfigCategory::Bandit { cooldown, .. }
+        | config::ConfigCategory::Bundle { cooldown, .. }
+        | config::ConfigCategory::Composite { cooldown, .. }
+        | config::ConfigCategory::Deck { cooldown, .. }
+        | config::ConfigCategory::Even { cooldown, .. }
+        | config::ConfigCategory::Gaussian { cooldown, .. }
+        | config::ConfigCategory::Geometric { cooldown, .. }
+        | config::ConfigCategory::Inventory { cooldown, .. }
+        | config::ConfigCategory::Lfu { cooldown, .. }
+        | config::ConfigCategory::Linear { cooldown, .. }
+        | config::ConfigCategory::Loot { cooldown, .. }
+        | config::ConfigCategory::Lottery { cooldown, .. }
+        | config::ConfigCategory::Lru { cooldown, .. }
+        | config::ConfigCategory::Markov { cooldown, .. }
+        | config::ConfigCategory::Pairs { cooldown, .. }
+        | config::ConfigCategory::Quota { cooldown, .. }
+        | config::ConfigCategory::Rated { cooldown, .. }
+        | config::ConfigCategory::Recency { cooldown, .. }
+        | config::ConfigCategory::SecretSanta { cooldown, .. }
+        | config::ConfigCategory::SpacedRepetition { cooldown, .. }
+        | config::ConfigCategory::Teams { cooldown, .. }
+        | config::ConfigCategory::Weighted { cooldown, .. }
+        | config::ConfigCategory::Zipf { cooldown, .. } => *cooldown,
+        config::ConfigCategory::Dice { .. }
+        | config::ConfigCategory::FloatRange { .. }
+        | config::ConfigCategory::IntegerRange { .. }
+        | config::ConfigCategory::Stratified { .. } => None,
+    }
+}
+
+/// Make sure that every category that can be reached from the last category in `path` exists, and
+/// that no category can be reached from itself.
+///
+/// # Arguments
+///
+/// * `config` - The config that contains the categories.
+/// * `path` - The categories we have followed to get to the category that should be checked,
+///   ending with that category. The category must exist in the config.
+fn check_references(
+    config: &BTreeMap<String, config::ConfigCategory>,
+    path: &mut Vec<String>,
+) -> Result<(), PickError<'static>> {
+    let category = &config[path.last().unwrap()];
+    check_outcome_choices(path.last().unwrap(), category)?;
+
+    for choice in category_choices(category) {
+        if let Some(nested) = &choice.options().category {
+            if path.contains(nested) {
+                path.push(nested.clone());
+                return Err(PickError::CategoryCycle(path.clone()));
+            }
+            if !config.contains_key(nested) {
+                return Err(PickError::ChoiceCategoryNotFound {
+                    choice: choice.name().to_string(),
+                    category: nested.clone(),
+                });
+            }
+
+            path.push(nested.clone());
+            check_references(config, path)?;
+            path.pop();
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that none of the choices of the given category refer to another category if its model
+/// produces a single outcome, since such a model doesn't pick a choice that could be followed. The
+/// groups of a stratified category are checked too. The `category` name is used in the error.
+fn check_outcome_choices(
+    category: &str,
+    config_category: &config::ConfigCategory,
+) -> Result<(), PickError<'static>> {
+    let model = match config_category {
+        config::ConfigCategory::Bundle { .. } => "bundle",
+        config::ConfigCategory::Loot { .. } => "loot",
+        config::ConfigCategory::Pairs { .. } => "pairs",
+        config::ConfigCategory::SecretSanta { .. } => "secret_santa",
+        config::ConfigCategory::Teams { .. } => "teams",
+        config::ConfigCategory::Stratified { groups } => {
+            return groups.iter().try_for_each(|group| {
+                check_outcome_choices(&format!("{} → {}", category, group.name), &group.category)
+            });
+        }
+        _ => return Ok(()),
+    };
+
+    match category_choices(config_category)
+        .iter()
+        .find(|c| c.options().category.is_some())
+    {
+        Some(choice) => Err(PickError::OutcomeChoiceCategory {
+            choice: choice.name().to_string(),
+            category: category.to_string(),
+            model,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Check that the model parameters of the given category are usable, so that its model can
+/// compute its chances. The `category` name is used in the error.
+fn check_parameters(
+    category: &str,
+    config_category: &config::ConfigCategory,
+) -> Result<(), PickError<'static>> {
+    match config_category {
+        config::ConfigCategory::Gaussian {
+            stddev_scaling_factor,
+            mean,
+            last_item_probability,
+            ..
+        } if !(stddev_scaling_factor.is_finite()
+            && *stddev_scaling_factor > 0.0
+            && mean.is_none_or(f64::is_finite)
+            && last_item_probability.is_none_or(|p| p > 0.0 && p < 1.0)) =>
+        {
+            Err(PickError::InvalidGaussian(category.to_string()))
+        }
+        config::ConfigCategory::Geometric { p, .. } if !(0.0..=1.0).contains(p) => {
+            Err(PickError::InvalidGeometric(category.to_string()))
+        }
+        config::ConfigCategory::Quota { tolerance, .. }
+            if !(tolerance.is_finite() && *tolerance >= 0.0) =>
+        {
+            Err(PickError::InvalidQuota(category.to_string()))
+        }
+        config::ConfigCategory::Recency { curve, .. } if !recency_curve_usable(*curve) => {
+            Err(PickError::InvalidRecency(category.to_string()))
+        }
+        config::ConfigCategory::Zipf { exponent, .. } if !exponent.is_finite() => {
+            Err(PickError::InvalidZipf(category.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Return how many distinct choices the given category could possibly pick, if only the choices
+/// that are marked in `eligible` may be picked.
+fn available_choices(category: &config::ConfigCategory, eligible: &[bool]) -> usize {
+    let can_pick: Vec<bool> = match category {
+        config::ConfigCategory::Composite {
+            combine,
+            factors,
+            choices,
+            ..
+        } => composite_weights(choices, eligible, factors, *combine)
+            .iter()
+            .map(|w| *w > 0.0)
+            .collect(),
+        config::ConfigCategory::Inventory { choices, .. } => {
+            choices.iter().map(|c| c.tickets > 0).collect()
+        }
+        config::ConfigCategory::Lottery { choices, .. } => {
+            choices.iter().map(|c| c.tickets > 0).collect()
+        }
+        config::ConfigCategory::Rated { choices, .. } => {
+            choices.iter().map(|c| c.rating > 0.0).collect()
+        }
+        config::ConfigCategory::Weighted { choices, .. } => {
+            choices.iter().map(|c| c.weight > 0).collect()
+        }
+        _ => vec![true; eligible.len()],
+    };
+
+    can_pick
+        .iter()
+        .zip(eligible)
+        .filter(|(c, e)| **c && **e)
+        .count()
+}
+
+/// The pairs that [`Engine::pair_up`] split a category into.
+#[derive(Debug, PartialEq)]
+pub struct Pairs {
+    /// The pairs, each listing its members in the order that they are listed in the category.
+    pub pairs: Vec<(String, String)>,
+    /// The choice that sits out, if there is an odd number of choices.
+    pub sitting_out: Option<String>,
+}
+
+/// The set of choices that [`Engine::assemble_bundle`] picked from a bundle category.
+#[derive(Debug, PartialEq)]
+pub struct Bundle {
+    /// The names of the choices in the set, in the order that they are listed in the category.
+    pub choices: Vec<String>,
+    /// The total cost of the choices in the set.
+    pub cost: f64,
+}
+
+/// Define the errors that can be returned from [`Engine::pick`].
+#[derive(Debug, Error)]
+pub enum PickError<'e> {
+    #[error("The category `{0}` was not found in the given config.")]
+    CategoryNotFound(&'e str),
+    #[error(
+        "The choice `{choice}` refers to the category `{category}`, which was not found in the \
+         given config."
+    )]
+    ChoiceCategoryNotFound { choice: String, category: String },
+    #[error(
+        "The category `{0}` cannot be rated, since only weighted and rated categories can be \
+         rated."
+    )]
+    CannotRate(&'e str),
+    #[error(
+        "The category `{0}` cannot be ordered, since only even, gaussian, inventory, lottery, lru, \
+         and weighted categories can be ordered."
+    )]
+    CannotOrder(&'e str),
+    #[error("No assignment of the category `{0}` avoids all of its exclusions and recent history.")]
+    NoAssignment(String),
+    #[error(
+        "The choices of the category `{0}` can't be split into teams that keep them together and \
+         apart as required."
+    )]
+    NoTeams(String),
+    #[error("None of the choices of the category `{0}` fit within its budget.")]
+    NoBundle(String),
+    #[error(
+        "The dice roll `{roll}` of the category `{category}` must be made of dice like `2d6` and \
+         whole numbers, joined by `+` or `-`, with at most {} dice of at most {} sides.",
+        MAX_DICE,
+        MAX_SIDES
+    )]
+    InvalidDice { category: String, roll: String },
+    #[error(
+        "The category `{0}` must have a minimum that isn't larger than its maximum, a step and \
+         standard deviation that are larger than 0, and a mean that is near enough to the range \
+         to reach it."
+    )]
+    InvalidRange(String),
+    #[error(
+        "The category `{0}` must roll at most {max} times, and its choices must reroll at most \
+         {max} times.",
+        max = MAX_ROLLS
+    )]
+    TooManyRolls(String),
+    #[error(
+        "The category `{0}` must have a stddev_scaling_factor that is larger than 0, a finite mean, \
+         and a last_item_probability between 0 and 1."
+    )]
+    InvalidGaussian(String),
+    #[error("The category `{0}` must have a p between 0 and 1.")]
+    InvalidGeometric(String),
+    #[error("The category `{0}` must have a tolerance that is a finite number of at least 0.")]
+    InvalidQuota(String),
+    #[error(
+        "The category `{0}` must have a doubling_days that is larger than 0, or a max_days of at \
+         least 0."
+    )]
+    InvalidRecency(String),
+    #[error("The category `{0}` must have an exponent that is a finite number.")]
+    InvalidZipf(String),
+    #[error("The category `{category}` must use the {model} model for this.")]
+    WrongModel {
+        category: &'e str,
+        model: &'static str,
+    },
+    #[error("The categories refer to each other in a cycle: {}.", .0.join(" → "))]
+    CategoryCycle(Vec<String>),
+    #[error(
+        "The choice `{choice}` of the category `{category}` can't refer to another category, \
+         because the {model} model doesn't pick a single choice."
+    )]
+    OutcomeChoiceCategory {
+        choice: String,
+        category: String,
+        model: &'static str,
+    },
+    #[error(
+        "Cannot pick {requested} distinct choices from the category `{category}`, which only has \
+         {available} available."
+    )]
+    NotEnoughChoices {
+        category: String,
+        requested: usize,
+        available: usize,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use approx::abs_diff_eq;
+    use mockall::{mock, predicate};
+    use rand::SeedableRng;
+
+    use super::*;
+
+    struct FakeRng(u32);
+
+    /// This allows our tests to have predictable results, and to have the same predictable results
+    /// on both 32-bit and 64-bit architectures.
+    impl rand::TryRng for FakeRng {
+        type Error = Infallible;
+
+        fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+            self.0 += 1;
+
+            Ok(self.0 - 1)
+        }
+
+        fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+            self.try_next_u32().map(|v| v as u64)
+        }
+
+        fn try_fill_bytes(&mut self, dst: &mut [u8]) -> Result<(), Self::Error> {
+            let mut left = dst;
+            while left.len() >= 4 {
+                let (l, r) = { left }.split_at_mut(4);
+                left = r;
+                let chunk: [u8; 4] = self.next_u32().to_le_bytes();
+                l.copy_from_slice(&chunk);
+            }
+            let n = left.len();
+            if n > 0 {
+                let chunk: [u8; 4] = self.next_u32().to_le_bytes();
+                left.copy_from_slice(&chunk[..n]);
+            }
+
+            Ok(())
+        }
+    }
+
+    mock! {
+        pub Ui {}
+
+        impl ui::Ui for Ui {
+            fn call_display_table(&self) -> bool;
+
+            fn display_table<'a>(&self, table: &ui::Table<'a>);
+
+            fn info(&self, message: &str);
+
+            fn prompt_choice(&self, choice: &str) -> bool;
+
+            fn prompt_comparison(&self, first: &str, second: &str) -> bool;
+
+            fn prompt_grade(&self, choice: &str) -> u8;
+        }
+    }
+
+    #[test]
+    fn test_get_consent() {
+        let mut ui = MockUi::default();
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec![
+                "you want this",
+                "you don't want this",
+            ]))
+            .times(2)
+            .returning(|x| !x.contains("don't"));
+        let mut engine = Engine::new(&ui);
+
+        assert!(engine.get_consent("you want this"));
+        assert!(!engine.get_consent("you don't want this"));
+    }
+
+    #[test]
+    fn test_pick() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["that", "this"]))
+            .times(2)
+            .returning(|c| c == "that");
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+        let category = config::ConfigCategory::Even {
+            choices,
+            cooldown: None,
+        };
+        let mut config = BTreeMap::new();
+        config.insert("things".to_string(), category);
+
+        let choice = engine.pick(&mut config, "things").unwrap();
+
+        assert_eq!(choice, "that");
+    }
+
+    #[test]
+    fn test_pick_many() {
+        // The user says no to the first one and yes to the second, and then yes to the first one
+        // when it is offered again for the second pick.
+        let mut ui = MockUi::default();
+        let mut answers = vec![true, true, false];
+        ui.expect_call_display_table().times(3).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["this", "that"]))
+            .times(3)
+            .returning(move |_| answers.pop().unwrap());
+        let mut engine = Engine::new(&ui);
+        let choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+        let category = config::ConfigCategory::Lru {
+            choices,
+            cooldown: None,
+        };
+        let mut config = BTreeMap::new();
+        config.insert("things".to_string(), category);
+
+        let picks = engine.pick_many(&mut config, "things", 2).unwrap();
+
+        assert_eq!(picks, vec!["that", "this"]);
+        assert_eq!(
+            config["things"],
+            config::ConfigCategory::Lru {
+                choices: vec![
+                    config::Choice::from("the other"),
+                    config::Choice::from("that"),
+                    config::Choice::from("this"),
+                ],
+                cooldown: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_pick_many_not_enough_choices() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let choices = vec![
+            config::WeightedChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 1,
+            },
+            config::WeightedChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 0,
+            },
+        ];
+        let category = config::ConfigCategory::Weighted {
+            choices,
+            cooldown: None,
+        };
+        let mut config = BTreeMap::new();
+        config.insert("things".to_string(), category);
+
+        match engine.pick_many(&mut config, "things", 2) {
+            Ok(_) => {
+                panic!("Picking more choices than are available should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "Cannot pick 2 distinct choices from the category `things`, which only has 1 \
+                     available."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_nonexistant_category() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+        let category = config::ConfigCategory::Even {
+            choices,
+            cooldown: None,
+        };
+        let mut config = BTreeMap::new();
+        config.insert("things".to_string(), category);
+
+        match engine.pick(&mut config, "does not exist") {
+            Ok(_) => {
+                panic!("The non-existant category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The category `does not exist` was not found in the given config."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_nested() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["restaurant", "Sitti"]))
+            .times(2)
+            .returning(|_| true);
+        ui.expect_info()
+            .with(predicate::eq("Picked: restaurant → Sitti"))
+            .times(1)
+            .return_const(());
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "dinner".to_string(),
+            config::ConfigCategory::Lru {
+                choices: vec![
+                    config::Choice {
+                        name: "restaurant".to_string(),
+                        options: config::ChoiceOptions {
+                            category: Some("restaurants".to_string()),
+                            ..Default::default()
+                        },
+                    },
+                    config::Choice::from("cook"),
+                ],
+                cooldown: None,
+            },
+        );
+        config.insert(
+            "restaurants".to_string(),
+            config::ConfigCategory::Lru {
+                choices: vec![
+                    config::Choice::from("Sitti"),
+                    config::Choice::from("Bida Manda"),
+                ],
+                cooldown: None,
+            },
+        );
+
+        let choice = engine.pick(&mut config, "dinner").unwrap();
+
+        assert_eq!(choice, "restaurant → Sitti");
+        assert_eq!(
+            config["restaurants"],
+            config::ConfigCategory::Lru {
+                choices: vec![
+                    config::Choice::from("Bida Manda"),
+                    config::Choice::from("Sitti"),
+                ],
+                cooldown: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_pick_nested_category_not_found() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "dinner".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![
+                    config::Choice::from("cook"),
+                    config::Choice {
+                        name: "restaurant".to_string(),
+                        options: config::ChoiceOptions {
+                            category: Some("restaurants".to_string()),
+                            ..Default::default()
+                        },
+                    },
+                ],
+                cooldown: None,
+            },
+        );
+
+        match engine.pick(&mut config, "dinner") {
+            Ok(_) => {
+                panic!("The missing nested category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The choice `restaurant` refers to the category `restaurants`, which was not \
+                     found in the given config."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_nested_cycle() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let nested = |name: &str, category: &str| config::Choice {
+            name: name.to_string(),
+            options: config::ChoiceOptions {
+                category: Some(category.to_string()),
+                ..Default::default()
+            },
+        };
+        let mut config = BTreeMap::new();
+        config.insert(
+            "a".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![config::Choice::from("plain"), nested("to b", "b")],
+                cooldown: None,
+            },
+        );
+        config.insert(
+            "b".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![nested("to c", "c")],
+                cooldown: None,
+            },
+        );
+        config.insert(
+            "c".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![nested("to b", "b")],
+                cooldown: None,
+            },
+        );
+
+        match engine.pick(&mut config, "a") {
+            Ok(_) => {
+                panic!("The cycle should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The categories refer to each other in a cycle: a → b → c → b."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_run_tournament() {
+        // The user always prefers the first choice of the pair.
+        let mut ui = MockUi::default();
+        ui.expect_prompt_comparison()
+            .times(3)
+            .returning(|_, _| true);
+        ui.expect_call_display_table().times(1).returning(|| false);
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Weighted {
+                choices: vec![
+                    config::WeightedChoice {
+                        name: "this".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        weight: 1,
+                    },
+                    config::WeightedChoice {
+                        name: "that".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        weight: 1,
+                    },
+                    config::WeightedChoice {
+                        name: "the other".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        weight: 1,
+                    },
+                ],
+                cooldown: None,
+            },
+        );
+
+        engine.run_tournament(&mut config, "things", 3).unwrap();
+
+        let config::ConfigCategory::Rated { choices, .. } = &config["things"] else {
+            panic!("The weighted category should have been turned into a rated category.");
+        };
+        // Each round compares two choices, and the least compared choice is always in the pair.
+        assert_eq!(choices.iter().map(|c| c.comparisons).sum::<u64>(), 6);
+        assert!(choices.iter().all(|c| c.comparisons > 0));
+        // Points move from the loser to the winner, so the total doesn't change.
+        assert!(abs_diff_eq!(
+            choices.iter().map(|c| c.rating).sum::<f64>(),
+            4500.0,
+            epsilon = 1e-9
+        ));
+        assert!(choices.iter().any(|c| c.rating != 1500.0));
+    }
+
+    #[test]
+    fn test_run_tournament_cannot_rate() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![config::Choice::from("this"), config::Choice::from("that")],
+                cooldown: None,
+            },
+        );
+
+        match engine.run_tournament(&mut config, "things", 1) {
+            Ok(_) => {
+                panic!("Rating an even category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The category `things` cannot be rated, since only weighted and rated \
+                     categories can be rated."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_order() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Lottery {
+                choices: vec![
+                    config::LotteryChoice {
+                        name: "this".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        reset: 0,
+                        tickets: 0,
+                        weight: 1,
+                    },
+                    config::LotteryChoice {
+                        name: "that".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        reset: 0,
+                        tickets: 2,
+                        weight: 4,
+                    },
+                    config::LotteryChoice {
+                        name: "the other".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        reset: 0,
+                        tickets: 3,
+                        weight: 9,
+                    },
+                ],
+                cooldown: None,
+            },
+        );
+
+        let result = engine.order(&mut config, "things", false).unwrap();
+
+        // The choice without any tickets goes last.
+        assert_eq!(result, vec!["that", "the other", "this"]);
+        let config::ConfigCategory::Lottery { choices, .. } = &config["things"] else {
+            panic!("The category should still be a lottery category.");
+        };
+        assert_eq!(
+            choices.iter().map(|c| c.tickets).collect::<Vec<_>>(),
+            vec![0, 2, 3]
+        );
+    }
+
+    /// Updating an ordered lottery category should run a round of the lottery for each choice, in
+    /// order.
+    #[test]
+    fn test_order_update() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Lottery {
+                choices: vec![
+                    config::LotteryChoice {
+                        name: "this".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        reset: 0,
+                        tickets: 0,
+                        weight: 1,
+                    },
+                    config::LotteryChoice {
+                        name: "that".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        reset: 0,
+                        tickets: 2,
+                        weight: 4,
+                    },
+                    config::LotteryChoice {
+                        name: "the other".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        reset: 0,
+                        tickets: 3,
+                        weight: 9,
+                    },
+                ],
+                cooldown: None,
+            },
+        );
+
+        let result = engine.order(&mut config, "things", true).unwrap();
+
+        assert_eq!(result, vec!["that", "the other", "this"]);
+        let config::ConfigCategory::Lottery { choices, .. } = &config["things"] else {
+            panic!("The category should still be a lottery category.");
+        };
+        assert_eq!(
+            choices.iter().map(|c| c.tickets).collect::<Vec<_>>(),
+            vec![0, 8, 9]
+        );
+    }
+
+    /// Updating an ordered lru category should leave its choices in the new order.
+    #[test]
+    fn test_order_update_lru() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Lru {
+                choices: vec![
+                    config::Choice {
+                        name: "this".to_string(),
+                        options: config::ChoiceOptions {
+                            tags: vec!["skip".to_string()],
+                            ..config::ChoiceOptions::default()
+                        },
+                    },
+                    config::Choice::from("that"),
+                    config::Choice::from("the other"),
+                ],
+                cooldown: None,
+            },
+        );
+        engine.set_tags(vec![], vec!["skip".to_string()]);
+
+        let result = engine.order(&mut config, "things", true).unwrap();
+
+        assert_eq!(result, vec!["that", "the other"]);
+        let config::ConfigCategory::Lru { choices, .. } = &config["things"] else {
+            panic!("The category should still be an lru category.");
+        };
+        assert_eq!(
+            choices.iter().map(|c| &c.name[..]).collect::<Vec<_>>(),
+            vec!["this", "that", "the other"]
+        );
+    }
+
+    #[test]
+    fn test_order_gaussian() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(rand::rngs::StdRng::seed_from_u64(1));
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Gaussian {
+                choices: (0..10)
+                    .map(|i| config::Choice::from(&format!("{}", i)[..]))
+                    .collect(),
+                stddev_scaling_factor: 3.0,
+                mean: None,
+                last_item_probability: None,
+                cooldown: None,
+            },
+        );
+        let mut first_positions = 0;
+
+        for _ in 0..100 {
+            let result = engine.order(&mut config, "things", false).unwrap();
+
+            let mut sorted = result.clone();
+            sorted.sort();
+            assert_eq!(
+                sorted,
+                (0..10).map(|i| format!("{}", i)).collect::<Vec<_>>()
+            );
+            first_positions += result.iter().position(|n| n == "0").unwrap();
+        }
+
+        // The first choice should tend to stay near the beginning. A shuffle would put it at position
+        // 4.5 on average.
+        assert!(first_positions < 300);
+    }
+
+    #[test]
+    fn test_order_cannot_order() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Dice {
+                roll: "2d6".to_string(),
+            },
+        );
+
+        match engine.order(&mut config, "things", false) {
+            Ok(_) => {
+                panic!("Ordering a dice category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The category `things` cannot be ordered, since only even, gaussian, \
+                     inventory, lottery, lru, and weighted categories can be ordered."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_elo_change() {
+        assert!(abs_diff_eq!(
+            elo_change(1500.0, 1500.0),
+            16.0,
+            epsilon = 1e-9
+        ));
+        // The favorite doesn't gain much by winning, but the underdog gains a lot.
+        assert!(abs_diff_eq!(
+            elo_change(1900.0, 1500.0),
+            2.909_090_909,
+            epsilon = 1e-6
+        ));
+        assert!(abs_diff_eq!(
+            elo_change(1500.0, 1900.0),
+            29.090_909_091,
+            epsilon = 1e-6
+        ));
+    }
+
+    #[test]
+    fn test_pair_up() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let (empty, never) = (|| ui::Cell::Text(""), || ui::Cell::Text("never"));
+                let row = |name, mut history: Vec<ui::Cell<'static>>, sat_out, partner| {
+                    history.insert(0, ui::Cell::Text(name));
+                    history.extend([ui::Cell::Unsigned(sat_out), ui::Cell::Text(partner)]);
+                    ui::Row {
+                        cells: history,
+                        chosen: false,
+                    }
+                };
+                let expected_table = ui::Table {
+                    footer: vec![],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Alice"),
+                        ui::Cell::Text("Bob"),
+                        ui::Cell::Text("Carol"),
+                        ui::Cell::Text("Dave"),
+                        ui::Cell::Text("Erin"),
+                        ui::Cell::Text("Sat Out"),
+                        ui::Cell::Text("Partner"),
+                    ],
+                    rows: vec![
+                        row(
+                            "Alice",
+                            vec![empty(), never(), ui::Cell::Unsigned(1), never(), never()],
+                            1,
+                            "Bob",
+                        ),
+                        row(
+                            "Bob",
+                            vec![never(), empty(), never(), ui::Cell::Unsigned(2), never()],
+                            1,
+                            "Alice",
+                        ),
+                        row(
+                            "Carol",
+                            vec![ui::Cell::Unsigned(1), never(), empty(), never(), never()],
+                            1,
+                            "Dave",
+                        ),
+                        row(
+                            "Dave",
+                            vec![never(), ui::Cell::Unsigned(2), never(), empty(), never()],
+                            1,
+                            "Carol",
+                        ),
+                        row(
+                            "Erin",
+                            vec![never(), never(), never(), never(), empty()],
+                            0,
+                            "sits out",
+                        ),
+                    ],
+                };
+                t == &expected_table
+            })
+            .times(1)
+            .return_const(());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("Alice & Bob, Carol & Dave; Erin sits out"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+devs:
+  model: pairs
+  rounds: 2
+  choices:
+    - name: Alice
+      last_paired:
+        Carol: 2
+      sat_out: 1
+    - name: Bob
+      last_paired:
+        Dave: 1
+      sat_out: 1
+    - name: Carol
+      last_paired:
+        Alice: 2
+      sat_out: 1
+    - name: Dave
+      last_paired:
+        Bob: 1
+      sat_out: 1
+    - name: Erin
+",
+        )
+        .unwrap();
+
+        let pairs = engine.pair_up(&mut config, "devs").unwrap();
+
+        assert_eq!(
+            pairs,
+            Pairs {
+                pairs: vec![
+                    ("Alice".to_string(), "Bob".to_string()),
+                    ("Carol".to_string(), "Dave".to_string())
+                ],
+                sitting_out: Some("Erin".to_string()),
+            }
+        );
+        let config::ConfigCategory::Pairs {
+            rounds, choices, ..
+        } = &config["devs"]
+        else {
+            panic!("The category should still be a pairs category.");
+        };
+        assert_eq!(*rounds, 3);
+        let history = |pairs: [(&str, u64); 2]| {
+            pairs
+                .iter()
+                .map(|(name, round)| (name.to_string(), *round))
+                .collect::<BTreeMap<_, _>>()
+        };
+        assert_eq!(
+            choices
+                .iter()
+                .map(|c| (c.name.as_str(), c.last_paired.clone(), c.sat_out))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Alice", history([("Bob", 3), ("Carol", 2)]), 1),
+                ("Bob", history([("Alice", 3), ("Dave", 1)]), 1),
+                ("Carol", history([("Alice", 2), ("Dave", 3)]), 1),
+                ("Dave", history([("Bob", 1), ("Carol", 3)]), 1),
+                ("Erin", BTreeMap::new(), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pair_up_avoids_recent_pairs() {
+        // Alice and Bob, and Carol and Dave, were paired in the last of 9 rounds, so each of the
+        // other pairs is 10 times as likely to be drawn first.
+        let repeats = (0..100)
+            .filter(|seed| {
+                let mut ui = MockUi::default();
+                ui.expect_call_display_table().returning(|| false);
+                ui.expect_prompt_choice().returning(|_| true);
+                let mut engine = Engine::new(&ui);
+                engine.set_rng(rand::rngs::StdRng::seed_from_u64(*seed));
+                let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+                    "
+devs:
+  model: pairs
+  rounds: 9
+  choices:
+    - name: Alice
+      last_paired:
+        Bob: 9
+    - name: Bob
+      last_paired:
+        Alice: 9
+    - name: Carol
+      last_paired:
+        Dave: 9
+    - name: Dave
+      last_paired:
+        Carol: 9
+",
+                )
+                .unwrap();
+
+                let pairs = engine.pair_up(&mut config, "devs").unwrap();
+
+                assert_eq!(pairs.pairs.len(), 2);
+                assert_eq!(pairs.sitting_out, None);
+                pairs.pairs[0] == ("Alice".to_string(), "Bob".to_string())
+            })
+            .count();
+
+        assert!(repeats < 15, "{} repeats", repeats);
+    }
+
+    #[test]
+    fn test_pair_up_wrong_model() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![config::Choice::from("this"), config::Choice::from("that")],
+                cooldown: None,
+            },
+        );
+
+        match engine.pair_up(&mut config, "things") {
+            Ok(_) => {
+                panic!("Pairing an even category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The category `things` must use the pairs model for this."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_assemble_bundle_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Decimal(5.0),
+                        ui::Cell::Text(""),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Cost"),
+                        ui::Cell::Text("Weight"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("chips"),
+                                ui::Cell::Decimal(3.0),
+                                ui::Cell::Unsigned(1),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("soda"),
+                                ui::Cell::Decimal(2.0),
+                                ui::Cell::Unsigned(2),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("candy"),
+                                ui::Cell::Decimal(10.0),
+                                ui::Cell::Unsigned(1),
+                            ],
+                            chosen: false,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_info()
+            .with(predicate::eq("Budget: 12.00, left over: 7.00"))
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("chips, soda (total cost 5.00)"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let bundle_choice = |name: &str, cost, weight| config::BundleChoice {
+            name: name.to_string(),
+            options: config::ChoiceOptions::default(),
+            cost,
+            weight,
+        };
+        let mut config = BTreeMap::new();
+        config.insert(
+            "snacks".to_string(),
+            config::ConfigCategory::Bundle {
+                budget: 12.0,
+                choices: vec![
+                    bundle_choice("chips", 3.0, 1),
+                    bundle_choice("soda", 2.0, 2),
+                    bundle_choice("candy", 10.0, 1),
+                    bundle_choice("cake", 25.0, 1),
+                ],
+                cooldown: None,
+            },
+        );
+
+        let bundle = engine.assemble_bundle(&mut config, "snacks").unwrap();
+
+        // Once chips and soda are in the bundle, candy no longer fits, and cake never did.
+        assert_eq!(
+            bundle,
+            Bundle {
+                choices: vec!["chips".to_string(), "soda".to_string()],
+                cost: 5.0,
+            }
+        );
+    }
+
+    #[test]
+    fn test_assemble_bundle_nothing_fits() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "snacks".to_string(),
+            config::ConfigCategory::Bundle {
+                budget: 1.0,
+                choices: vec![
+                    config::BundleChoice {
+                        name: "cake".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        cost: 25.0,
+                        weight: 1,
+                    },
+                    // Choices with a weight of 0 are never picked, even if they fit.
+                    config::BundleChoice {
+                        name: "water".to_string(),
+                        options: config::ChoiceOptions::default(),
+                        cost: 0.0,
+                        weight: 0,
+                    },
+                ],
+                cooldown: None,
+            },
+        );
+
+        match engine.assemble_bundle(&mut config, "snacks") {
+            Ok(_) => {
+                panic!("A bundle that nothing fits in should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "None of the choices of the category `snacks` fit within its budget."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_assemble_bundle_wrong_model() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![config::Choice::from("this"), config::Choice::from("that")],
+                cooldown: None,
+            },
+        );
+
+        match engine.assemble_bundle(&mut config, "things") {
+            Ok(_) => {
+                panic!("Bundling an even category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The category `things` must use the bundle model for this."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_stratified() {
+        // The user says no to the first vegetable, so each group has its own accept loop.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(4).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["tofu", "rice", "kale", "leeks"]))
+            .times(4)
+            .returning(|option| option != "kale");
+        ui.expect_info()
+            .with(predicate::eq(
+                "Picked: protein: tofu; starch: rice; vegetable: leeks",
+            ))
+            .times(1)
+            .return_const(());
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config = BTreeMap::new();
+        config.insert(
+            "meal".to_string(),
+            config::ConfigCategory::Stratified {
+                groups: vec![
+                    config::StratifiedGroup {
+                        name: "protein".to_string(),
+                        category: config::ConfigCategory::Weighted {
+                            choices: vec![config::WeightedChoice {
+                                name: "tofu".to_string(),
+                                options: config::ChoiceOptions::default(),
+                                weight: 1,
+                            }],
+                            cooldown: None,
+                        },
+                    },
+                    config::StratifiedGroup {
+                        name: "starch".to_string(),
+                        category: config::ConfigCategory::Lru {
+                            choices: vec![
+                                config::Choice::from("rice"),
+                                config::Choice::from("pasta"),
+                            ],
+                            cooldown: None,
+                        },
+                    },
+                    config::StratifiedGroup {
+                        name: "vegetable".to_string(),
+                        category: config::ConfigCategory::Lru {
+                            choices: vec![
+                                config::Choice::from("kale"),
+                                config::Choice::from("leeks"),
+                            ],
+                            cooldown: None,
+                        },
+                    },
+                ],
+            },
+        );
+
+        let picks = engine.pick_stratified(&mut config, "meal").unwrap();
+
+        assert_eq!(
+            picks,
+            vec![
+                ("protein".to_string(), "tofu".to_string()),
+                ("starch".to_string(), "rice".to_string()),
+                ("vegetable".to_string(), "leeks".to_string()),
+            ]
+        );
+        let config::ConfigCategory::Stratified { groups } = &config["meal"] else {
+            panic!("The category should still be a stratified category.");
+        };
+        // Each group's lru state should have been updated on its own.
+        assert_eq!(
+            groups[1].category,
+            config::ConfigCategory::Lru {
+                choices: vec![config::Choice::from("pasta"), config::Choice::from("rice")],
+                cooldown: None,
+            }
+        );
+        assert_eq!(
+            groups[2].category,
+            config::ConfigCategory::Lru {
+                choices: vec![config::Choice::from("kale"), config::Choice::from("leeks")],
+                cooldown: None,
+            }
+        );
+    }
+
+    /// Picking from a stratified category with pick() should express the picks as a single line.
+    #[test]
+    fn test_pick_stratified_single_line() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice().times(2).returning(|_| true);
+        ui.expect_info()
+            .with(predicate::eq("Picked: starch: rice; vegetable: kale"))
+            .times(1)
+            .return_const(());
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config = BTreeMap::new();
+        config.insert(
+            "meal".to_string(),
+            config::ConfigCategory::Stratified {
+                groups: vec![
+                    config::StratifiedGroup {
+                        name: "starch".to_string(),
+                        category: config::ConfigCategory::Lru {
+                            choices: vec![config::Choice::from("rice")],
+                            cooldown: None,
+                        },
+                    },
+                    config::StratifiedGroup {
+                        name: "vegetable".to_string(),
+                        category: config::ConfigCategory::Lru {
+                            choices: vec![config::Choice::from("kale")],
+                            cooldown: None,
+                        },
+                    },
+                ],
+            },
+        );
+
+        let pick = engine.pick(&mut config, "meal").unwrap();
+
+        assert_eq!(pick, "starch: rice; vegetable: kale");
+    }
+
+    #[test]
+    fn test_pick_stratified_wrong_model() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![config::Choice::from("this"), config::Choice::from("that")],
+                cooldown: None,
+            },
+        );
+
+        match engine.pick_stratified(&mut config, "things") {
+            Ok(_) => {
+                panic!("Picking groups from an even category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The category `things` must use the stratified model for this."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_secret_santa() {
+        // Alice may not give to Bob, which leaves only one possible assignment.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::eq("Alice → Carol, Bob → Alice, Carol → Bob"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "santa".to_string(),
+            config::ConfigCategory::SecretSanta {
+                avoid_rounds: 1,
+                history: vec![],
+                choices: vec![
+                    config::SecretSantaChoice {
+                        name: "Alice".to_string(),
+                        exclude: vec!["Bob".to_string()],
+                        options: config::ChoiceOptions::default(),
+                    },
+                    config::SecretSantaChoice {
+                        name: "Bob".to_string(),
+                        exclude: vec![],
+                        options: config::ChoiceOptions::default(),
+                    },
+                    config::SecretSantaChoice {
+                        name: "Carol".to_string(),
+                        exclude: vec![],
+                        options: config::ChoiceOptions::default(),
+                    },
+                ],
+                cooldown: None,
+            },
+        );
+
+        let assignment = engine.assign_secret_santa(&mut config, "santa").unwrap();
+
+        let expected = vec![
+            ("Alice".to_string(), "Carol".to_string()),
+            ("Bob".to_string(), "Alice".to_string()),
+            ("Carol".to_string(), "Bob".to_string()),
+        ];
+        assert_eq!(assignment, expected);
+        let config::ConfigCategory::SecretSanta { history, .. } = &config["santa"] else {
+            panic!("The category changed models.");
+        };
+        assert_eq!(history, &vec![expected.into_iter().collect()]);
+    }
+
+    #[test]
+    fn test_assign_secret_santa_impossible() {
+        // The only assignment that avoids Alice's exclusion was used last round.
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+santa:
+  model: secret_santa
+  history:
+    - Alice: Carol
+      Bob: Alice
+      Carol: Bob
+  choices:
+    - name: Alice
+      exclude: [Bob]
+    - name: Bob
+    - name: Carol
+",
+        )
+        .unwrap();
+
+        match engine.pick(&mut config, "santa") {
+            Ok(_) => {
+                panic!("An impossible assignment should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "No assignment of the category `santa` avoids all of its exclusions and recent \
+                     history."
+                );
+            }
+        }
+    }
+
+    /// The choices of a model that produces a single outcome can't refer to other categories,
+    /// whether the category is picked from directly, through a stratified group, or with its own
+    /// method.
+    #[test]
+    fn test_pick_outcome_nested() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+santa:
+  model: secret_santa
+  choices:
+    - name: Alice
+    - name: Bob
+      category: gifts
+gifts:
+  model: even
+  choices:
+    - socks
+party:
+  model: stratified
+  groups:
+    - name: gift
+      model: loot
+      choices:
+        - name: surprise
+          weight: 1
+          category: gifts
+",
+        )
+        .unwrap();
+
+        for (category, expected) in [
+            (
+                "santa",
+                "The choice `Bob` of the category `santa` can't refer to another category, because \
+                 the secret_santa model doesn't pick a single choice.",
+            ),
+            (
+                "party",
+                "The choice `surprise` of the category `party → gift` can't refer to another \
+                 category, because the loot model doesn't pick a single choice.",
+            ),
+        ] {
+            match engine.pick(&mut config, category) {
+                Ok(_) => {
+                    panic!("The nested category should have returned an error.");
+                }
+                Err(error) => {
+                    assert_eq!(format!("{}", error), expected);
+                }
+            }
+        }
+        match engine.assign_secret_santa(&mut config, "santa") {
+            Ok(_) => {
+                panic!("The nested category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The choice `Bob` of the category `santa` can't refer to another category, \
+                     because the secret_santa model doesn't pick a single choice."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_secret_santa_wrong_model() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![config::Choice::from("this"), config::Choice::from("that")],
+                cooldown: None,
+            },
+        );
+
+        match engine.assign_secret_santa(&mut config, "things") {
+            Ok(_) => {
+                panic!("Assigning an even category should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The category `things` must use the secret_santa model for this."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_teams() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice().times(1).returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(rand::rngs::StdRng::seed_from_u64(7));
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+game:
+  model: teams
+  together:
+    - [Erin, Frank]
+  apart:
+    - [Erin, Grace]
+  choices:
+    - name: Alice
+      skill: 1
+    - name: Bob
+      skill: 2
+    - name: Carol
+      skill: 3
+    - name: Dave
+      skill: 4
+    - name: Erin
+      skill: 0
+    - name: Frank
+      skill: 0
+    - name: Grace
+      skill: 0
+    - name: Heidi
+      skill: 0
+",
+        )
+        .unwrap();
+
+        let mut teams = engine.split_teams(&mut config, "game").unwrap();
+
+        // Both teams should have a total skill of 5, so Alice must be with Dave.
+        teams.sort();
+        assert_eq!(teams.len(), 2);
+        let alice = teams
+            .iter()
+            .find(|t| t.contains(&"Alice".to_string()))
+            .unwrap();
+        assert!(alice.contains(&"Dave".to_string()));
+        assert!(!alice.contains(&"Bob".to_string()));
+        // Each team has 4 members, Erin and Frank are together, and Grace is on the other team.
+        assert!(teams.iter().all(|t| t.len() == 4));
+        let erin = teams
+            .iter()
+            .find(|t| t.contains(&"Erin".to_string()))
+            .unwrap();
+        assert!(erin.contains(&"Frank".to_string()));
+        assert!(!erin.contains(&"Grace".to_string()));
+    }
+
+    #[test]
+    fn test_split_teams_impossible() {
+        // Three people can't all be on different teams when there are only two teams.
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+game:
+  model: teams
+  teams: 2
+  apart:
+    - [Alice, Bob, Carol]
+  choices:
+    - name: Alice
+    - name: Bob
+    - name: Carol
+    - name: Dave
+",
+        )
+        .unwrap();
+
+        match engine.pick(&mut config, "game") {
+            Ok(_) => {
+                panic!("Impossible teams should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The choices of the category `game` can't be split into teams that keep them \
+                     together and apart as required."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_teams_cooldown() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice().times(1).returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+game:
+  model: teams
+  cooldown:
+    picks: 2
+  choices:
+    - name: Alice
+    - name: Bob
+    - name: Carol
+    - name: Dave
+    - name: Erin
+      cooling:
+        picks: 1
+",
+        )
+        .unwrap();
+
+        engine.split_teams(&mut config, "game").unwrap();
+
+        // Everyone who played starts cooling down, and Erin counts down a single split.
+        let cooling = category_choices(&config["game"])
+            .into_iter()
+            .map(|c| c.options().cooling.as_ref().map(|c| c.picks))
+            .collect::<Vec<_>>();
+        assert_eq!(cooling, vec![Some(2), Some(2), Some(2), Some(2), None]);
+    }
+
+    #[test]
+    fn test_team_count() {
+        assert_eq!(team_count(&[true; 7], None, None), 2);
+        assert_eq!(team_count(&[true; 7], Some(3), Some(2)), 3);
+        assert_eq!(team_count(&[true; 7], None, Some(2)), 4);
+        assert_eq!(team_count(&[true, true, false, false], None, Some(2)), 1);
+        assert_eq!(team_count(&[true; 7], Some(0), None), 1);
+    }
+
+    #[test]
+    fn test_pick_tags() {
+        // Only "that" has the vegetarian tag without the closed tag, so it must be offered first
+        // even though "this" is the least recently used.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::eq("that"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_tags(vec!["vegetarian".to_string()], vec!["closed".to_string()]);
+        let tagged = |name: &str, tags: &[&str]| config::Choice {
+            name: name.to_string(),
+            options: config::ChoiceOptions {
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            },
+        };
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Lru {
+                choices: vec![
+                    tagged("this", &[]),
+                    tagged("that", &["vegetarian", "cheap"]),
+                    tagged("the other", &["vegetarian", "closed"]),
+                    tagged("another", &[]),
+                ],
+                cooldown: None,
+            },
+        );
+
+        let choice = engine.pick(&mut config, "things").unwrap();
+
+        assert_eq!(choice, "that");
+        // The choices that didn't match keep their order.
+        assert_eq!(
+            config["things"],
+            config::ConfigCategory::Lru {
+                choices: vec![
+                    tagged("this", &[]),
+                    tagged("the other", &["vegetarian", "closed"]),
+                    tagged("another", &[]),
+                    tagged("that", &["vegetarian", "cheap"]),
+                ],
+                cooldown: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_pick_tags_not_enough_choices() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        engine.set_tags(vec!["vegetarian".to_string()], vec![]);
+        let choices = vec![config::Choice::from("this"), config::Choice::from("that")];
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Even {
+                choices,
+                cooldown: None,
+            },
+        );
+
+        match engine.pick(&mut config, "things") {
+            Ok(_) => {
+                panic!("Picking with a filter that matches nothing should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "Cannot pick 1 distinct choices from the category `things`, which only has 0 \
+                     available."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_schedule_verbose() {
+        // "this" is only available on Mondays, and it's a Friday, so it should be listed as
+        // unavailable at the bottom of the table.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Unsigned(4),
+                        ui::Cell::Float(100.0),
+                        ui::Cell::Text(""),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                        ui::Cell::Text("Status"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Unsigned(1),
+                                ui::Cell::Float(25.0),
+                                ui::Cell::Text(""),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Unsigned(3),
+                                ui::Cell::Float(75.0),
+                                ui::Cell::Text(""),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Text(""),
+                                ui::Cell::Text(""),
+                                ui::Cell::Text("unavailable"),
+                            ],
+                            chosen: false,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("that"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        engine.set_now(DateTime::parse_from_rfc3339("2026-10-16T12:00:00+00:00").unwrap());
+        let choices = vec![
+            config::WeightedChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions {
+                    schedule: Some(config::Schedule {
+                        days: vec![chrono::Weekday::Mon],
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                weight: 1,
+            },
+            config::WeightedChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 1,
+            },
+            config::WeightedChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 3,
+            },
+        ];
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Weighted {
+                choices,
+                cooldown: None,
+            },
+        );
+
+        let choice = engine.pick(&mut config, "things").unwrap();
+
+        assert_eq!(choice, "that");
+    }
+
+    #[test]
+    fn test_pick_cooldown() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice().times(2).returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_now(DateTime::parse_from_rfc3339("2026-10-16T12:00:00+00:00").unwrap());
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Even {
+                choices: vec![
+                    config::Choice::from("this"),
+                    config::Choice {
+                        name: "that".to_string(),
+                        options: config::ChoiceOptions {
+                            cooldown: Some(config::Cooldown {
+                                days: 1,
+                                hours: 2,
+                                ..Default::default()
+                            }),
+                            ..Default::default()
+                        },
+                    },
+                ],
+                cooldown: Some(config::Cooldown {
+                    picks: 1,
+                    ..Default::default()
+                }),
+            },
+        );
+
+        let first = engine.pick(&mut config, "things").unwrap();
+        let second = engine.pick(&mut config, "things").unwrap();
+
+        // The first choice is cooling down during the second pick, so the other must be chosen.
+        assert_ne!(first, second);
+        let cooling = |name: &str| {
+            category_choices(&config["things"])
+                .into_iter()
+                .find(|c| c.name() == name)
+                .unwrap()
+                .options()
+                .cooling
+                .clone()
+        };
+        // "this" uses the category's cooldown, and "that" uses its own.
+        if first == "this" {
+            assert_eq!(cooling("this"), None);
+            assert_eq!(
+                cooling("that"),
+                Some(config::Cooling {
+                    picks: 0,
+                    until: Some(DateTime::parse_from_rfc3339("2026-10-17T14:00:00+00:00").unwrap())
+                })
+            );
+        } else {
+            assert_eq!(
+                cooling("this"),
+                Some(config::Cooling {
+                    picks: 1,
+                    until: None
+                })
+            );
+            assert_eq!(
+                cooling("that"),
+                Some(config::Cooling {
+                    picks: 0,
+                    until: Some(DateTime::parse_from_rfc3339("2026-10-17T14:00:00+00:00").unwrap())
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn test_pick_cooldown_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Unsigned(3),
+                        ui::Cell::Float(100.0),
+                        ui::Cell::Text(""),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                        ui::Cell::Text("Status"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Unsigned(3),
+                                ui::Cell::Float(100.0),
+                                ui::Cell::Text(""),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Text(""),
+                                ui::Cell::Text(""),
+                                ui::Cell::Text("cooling down for 2 more picks"),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Text(""),
+                                ui::Cell::Text(""),
+                                ui::Cell::Text("cooling down until 2026-10-17 09:30"),
+                            ],
+                            chosen: false,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("the other"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        engine.set_now(DateTime::parse_from_rfc3339("2026-10-16T12:00:00+00:00").unwrap());
+        let until = DateTime::parse_from_rfc3339("2026-10-17T09:30:00+00:00").unwrap();
+        let choices = vec![
+            config::WeightedChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions {
+                    cooling: Some(config::Cooling {
+                        picks: 2,
+                        until: None,
+                    }),
+                    ..Default::default()
+                },
+                weight: 1,
+            },
+            config::WeightedChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions {
+                    cooling: Some(config::Cooling {
+                        picks: 0,
+                        until: Some(until),
+                    }),
+                    ..Default::default()
+                },
+                weight: 1,
+            },
+            config::WeightedChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 3,
+            },
+        ];
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Weighted {
+                choices,
+                cooldown: None,
+            },
+        );
+
+        let choice = engine.pick(&mut config, "things").unwrap();
+
+        assert_eq!(choice, "the other");
+        let cooling = category_choices(&config["things"])
+            .iter()
+            .map(|c| c.options().cooling.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            cooling,
+            vec![
+                Some(config::Cooling {
+                    picks: 1,
+                    until: None
+                }),
+                Some(config::Cooling {
+                    picks: 0,
+                    until: Some(until)
+                }),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pick_bandit() {
+        // The user rejects the first choice and accepts the second.
+        let mut ui = MockUi::default();
+        let mut answers = vec![true, false];
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["that", "the other"]))
+            .times(2)
+            .returning(move |_| answers.pop().unwrap());
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(rand::rngs::StdRng::seed_from_u64(42));
+        let mut choices = vec![
+            config::BanditChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                accepted: 0,
+                rejected: 30,
+            },
+            config::BanditChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                accepted: 30,
+                rejected: 0,
+            },
+            config::BanditChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                accepted: 1,
+                rejected: 1,
+            },
+        ];
+
+        let result = engine.pick_bandit(&mut choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![2]);
+        assert_eq!(
+            choices,
+            vec![
+                config::BanditChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    accepted: 0,
+                    rejected: 30,
+                },
+                config::BanditChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    accepted: 30,
+                    rejected: 1,
+                },
+                config::BanditChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    accepted: 2,
+                    rejected: 1,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thompson_weights() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(rand::rngs::StdRng::seed_from_u64(42));
+        let choices = vec![
+            config::BanditChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                accepted: 0,
+                rejected: 30,
+            },
+            config::BanditChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                accepted: 30,
+                rejected: 0,
+            },
+            config::BanditChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                accepted: 0,
+                rejected: 0,
+            },
+        ];
+
+        let weights = engine.thompson_weights(&choices, &[0, 1, 2]);
+
+        // Every round has a winner, and each candidate gets an extra win.
+        assert_eq!(weights.iter().sum::<u64>(), THOMPSON_ROUNDS + 3);
+        // The choice that was never rejected should win nearly every round, and the choice that
+        // was always rejected should never win.
+        assert!(weights[1] > 900);
+        assert_eq!(weights[0], 1);
+    }
+
+    #[test]
+    /// Test pick_by_position() with the verbose flag set, and with a choice that isn't eligible
+    fn test_pick_by_position_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Decimal(1.75),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Decimal(0.25),
+                                ui::Cell::Float(14.286),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Decimal(0.5),
+                                ui::Cell::Float(28.571),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Float(57.143),
+                            ],
+                            chosen: true,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("nope"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+        // The positions only count the eligible choices, so "that" is in the second position.
+        let result = engine.pick_by_position(
+            &mut choices,
+            &[true, false, true, true],
+            &|position, _| 0.5_f64.powi(position as i32),
+            1,
+        );
+        assert_eq!(result, vec![3]);
+        assert_eq!(
+            choices,
+            vec![
+                config::Choice::from("nope"),
+                config::Choice::from("that"),
+                config::Choice::from("the other"),
+                config::Choice::from("this"),
+            ]
+        );
+    }
+
+    #[test]
+    /// Test the linear model with the verbose flag set
+    fn test_pick_linear_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Decimal(6.0),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Float(16.667),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Decimal(2.0),
+                                ui::Cell::Float(33.333),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Decimal(3.0),
+                                ui::Cell::Float(50.0),
+                            ],
+                            chosen: true,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Linear {
+                choices: vec![
+                    config::Choice::from("this"),
+                    config::Choice::from("that"),
+                    config::Choice::from("the other"),
+                ],
+                cooldown: None,
+            },
+        );
+        let choice = engine.pick(&mut config, "things").unwrap();
+        assert_eq!(choice, "this");
+        let config::ConfigCategory::Linear { choices, .. } = &config["things"] else {
+            panic!("The category changed models.");
+        };
+        assert_eq!(
+            choices,
+            &vec![
+                config::Choice::from("that"),
+                config::Choice::from("the other"),
+                config::Choice::from("this"),
+            ]
+        );
+    }
+
+    #[test]
+    /// Test the zipf model with the verbose flag set
+    fn test_pick_zipf_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Decimal(1.361),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Decimal(0.111),
+                                ui::Cell::Float(8.163),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Decimal(0.25),
+                                ui::Cell::Float(18.367),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Float(73.469),
+                            ],
+                            chosen: true,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config = BTreeMap::new();
+        config.insert(
+            "things".to_string(),
+            config::ConfigCategory::Zipf {
+                exponent: 2.0,
+                choices: vec![
+                    config::Choice::from("this"),
+                    config::Choice::from("that"),
+                    config::Choice::from("the other"),
+                ],
+                cooldown: None,
+            },
+        );
+        let choice = engine.pick(&mut config, "things").unwrap();
+        assert_eq!(choice, "this");
+        let config::ConfigCategory::Zipf { choices, .. } = &config["things"] else {
+            panic!("The category changed models.");
+        };
+        assert_eq!(
+            choices,
+            &vec![
+                config::Choice::from("that"),
+                config::Choice::from("the other"),
+                config::Choice::from("this"),
+            ]
+        );
+    }
+
+    #[test]
+    /// Test pick_composite() with the verbose flag set
+    fn test_pick_composite_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                // "that" has no tickets, so it has no chance of being picked.
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Text(""),
+                        ui::Cell::Text(""),
+                        ui::Cell::Text(""),
+                        ui::Cell::Text(""),
+                        ui::Cell::Decimal(8.0),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Static Weight"),
+                        ui::Cell::Text("LRU Decay"),
+                        ui::Cell::Text("Tickets"),
+                        ui::Cell::Text("Tag Boost"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Decimal(4.0),
+                                ui::Cell::Decimal(0.25),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Decimal(2.0),
+                                ui::Cell::Decimal(2.0),
+                                ui::Cell::Float(25.0),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Decimal(2.0),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Decimal(3.0),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Decimal(6.0),
+                                ui::Cell::Float(75.0),
+                            ],
+                            chosen: true,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::CompositeChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 2,
+                tickets: 3,
+            },
+            config::CompositeChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 1,
+                tickets: 0,
+            },
+            config::CompositeChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions {
+                    tags: vec!["spicy".to_string(), "cheap".to_string()],
+                    ..Default::default()
+                },
+                weight: 4,
+                tickets: 1,
+            },
+        ];
+        let factors = vec![
+            config::CompositeFactor::Weight,
+            config::CompositeFactor::Lru { decay: 0.5 },
+            config::CompositeFactor::Lottery {
+                increment: 1,
+                reset: 0,
+            },
+            config::CompositeFactor::Tags {
+                boosts: BTreeMap::from([("spicy".to_string(), 2.0)]),
+            },
+        ];
+
+        let result = engine.pick_composite(
+            &mut choices,
+            &[true; 3],
+            &factors,
+            config::Combine::Multiply,
+            1,
+        );
+
+        // The lru factor moves "this" to the end, and the lottery factor resets its tickets.
+        assert_eq!(result, vec![2]);
+        assert_eq!(
+            choices
+                .iter()
+                .map(|c| (c.name.as_str(), c.tickets))
+                .collect::<Vec<_>>(),
+            vec![("that", 1), ("the other", 2), ("this", 0)]
+        );
+    }
+
+    #[test]
+    fn test_composite_weights() {
+        let choices = vec![
+            config::CompositeChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 3,
+                tickets: 0,
+            },
+            config::CompositeChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions {
+                    tags: vec!["spicy".to_string()],
+                    ..Default::default()
+                },
+                weight: 1,
+                tickets: 5,
+            },
+        ];
+        let factors = vec![
+            config::CompositeFactor::Weight,
+            config::CompositeFactor::Lottery {
+                increment: 1,
+                reset: 0,
+            },
+            config::CompositeFactor::Tags {
+                boosts: BTreeMap::from([("spicy".to_string(), 1.5)]),
+            },
+        ];
+
+        assert_eq!(
+            composite_weights(&choices, &[true; 2], &factors, config::Combine::Multiply),
+            vec![0.0, 7.5]
+        );
+        assert_eq!(
+            composite_weights(&choices, &[true; 2], &factors, config::Combine::Add),
+            vec![4.0, 7.5]
+        );
+        assert_eq!(
+            composite_weights(&choices, &[true; 2], &[], config::Combine::Add),
+            vec![1.0, 1.0]
+        );
+    }
+
+    #[test]
+    fn test_composite_factor_lru_skips_ineligible() {
+        let choices = ["this", "that", "the other"]
+            .into_iter()
+            .map(|name| config::CompositeChoice {
+                name: name.to_string(),
+                options: config::ChoiceOptions::default(),
+                weight: 1,
+                tickets: 0,
+            })
+            .collect::<Vec<_>>();
+        let factor = config::CompositeFactor::Lru { decay: 0.5 };
+
+        // Since the first choice can't be picked, the second choice is at the front of the list.
+        assert_eq!(
+            composite_factor(&choices, &[false, true, true], &factor),
+            vec![1.0, 1.0, 0.5]
+        );
+        assert_eq!(
+            composite_factor(&choices, &[true; 3], &factor),
+            vec![1.0, 0.5, 0.25]
+        );
+    }
+
+    #[test]
+    fn test_pick_deck() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::eq("that"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::DeckChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                drawn: true,
+            },
+            config::DeckChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                drawn: false,
+            },
+            config::DeckChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                drawn: false,
+            },
+        ];
+
+        let result = engine.pick_deck(&mut choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![1]);
+        assert_eq!(
+            choices,
+            vec![
+                config::DeckChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    drawn: true,
+                },
+                config::DeckChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    drawn: true,
+                },
+                config::DeckChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    drawn: false,
+                },
+            ]
+        );
+    }
+
+    /// When the last choice is drawn from the deck during a pick_many(), the deck should get
+    /// reshuffled without the choices that were already picked in this run.
+    #[test]
+    fn test_pick_deck_reshuffle() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Unsigned(3),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("In Deck"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Boolean(true),
+                                ui::Cell::Float(50.0),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Boolean(true),
+                                ui::Cell::Float(0.0),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Boolean(true),
+                                ui::Cell::Float(50.0),
+                            ],
+                            chosen: false,
+                        },
+                    ],
+                };
+                // The first table is before the reshuffle, and the second is after.
+                t.rows[0].cells[1] == ui::Cell::Boolean(false) || tables_equal(t, &expected_table)
+            })
+            .times(2)
+            .returning(|_| ());
+        ui.expect_info()
+            .with(predicate::eq(
+                "The deck is empty, so it has been reshuffled.",
+            ))
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["this", "that"]))
+            .times(2)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::DeckChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                drawn: true,
+            },
+            config::DeckChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                drawn: false,
+            },
+            config::DeckChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                drawn: true,
+            },
+        ];
+
+        let result = engine.pick_deck(&mut choices, &[true; 3], 2);
+
+        assert_eq!(result, vec![1, 0]);
+        assert_eq!(
+            choices,
+            vec![
+                config::DeckChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    drawn: true,
+                },
+                config::DeckChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    drawn: false,
+                },
+                config::DeckChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    drawn: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pick_dice_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let row = |value, chance, chosen| ui::Row {
+                    cells: vec![ui::Cell::Text(value), ui::Cell::Float(chance)],
+                    chosen,
+                };
+                let expected_table = ui::Table {
+                    footer: vec![ui::Cell::Text("Total"), ui::Cell::Float(100.0)],
+                    header: vec![ui::Cell::Text("Value"), ui::Cell::Text("Chance")],
+                    rows: vec![
+                        row("0", 100.0 / 24.0, false),
+                        row("1", 200.0 / 24.0, false),
+                        row("2", 300.0 / 24.0, false),
+                        row("3", 400.0 / 24.0, true),
+                        row("4", 400.0 / 24.0, false),
+                        row("5", 400.0 / 24.0, false),
+                        row("6", 300.0 / 24.0, false),
+                        row("7", 200.0 / 24.0, false),
+                        row("8", 100.0 / 24.0, false),
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("3"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+
+        let result = engine.pick_dice("dice", "d6 - d4 + 3");
+
+        assert_eq!(result.unwrap(), "3");
+    }
+
+    #[test]
+    fn test_pick_dice_invalid() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+
+        match engine.pick_dice("dice", "2d6+d") {
+            Ok(_) => {
+                panic!("Rolling a die without sides should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "The dice roll `2d6+d` of the category `dice` must be made of dice like `2d6` \
+                     and whole numbers, joined by `+` or `-`, with at most 100 dice of at most \
+                     1000 sides."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_dice() {
+        assert_eq!(parse_dice("2d6+1"), Some((vec![(2, 6)], 1)));
+        assert_eq!(parse_dice(" D20 "), Some((vec![(1, 20)], 0)));
+        assert_eq!(
+            parse_dice("-1d4 + 3d8 - 2 + 5"),
+            Some((vec![(-1, 4), (3, 8)], 3))
+        );
+        assert_eq!(parse_dice("7"), Some((vec![], 7)));
+        assert_eq!(parse_dice("100d1000"), Some((vec![(100, 1000)], 0)));
+        assert_eq!(
+            parse_dice("9223372036854675807+100d1000"),
+            Some((vec![(100, 1000)], 9223372036854675807))
+        );
+        for roll in [
+            "",
+            "2d",
+            "d6+",
+            "0d6",
+            "2d0",
+            "2x6",
+            "1--2",
+            "60d6+41d6",
+            "101d6",
+            "1d1001",
+            "18446744073709551615d6",
+            "-9223372036854775808d6",
+            "1d18446744073709551615",
+            "9223372036854775807+1d6",
+            "-1d6-9223372036854775807",
+        ] {
+            assert_eq!(parse_dice(roll), None, "{}", roll);
+        }
+    }
+
+    #[test]
+    fn test_dice_chances() {
+        let (lowest, chances) = dice_chances(&[(2, 6)], 1);
+
+        assert_eq!(lowest, 3);
+        let expected = [1., 2., 3., 4., 5., 6., 5., 4., 3., 2., 1.];
+        assert_eq!(chances.len(), expected.len());
+        for (chance, ways) in chances.iter().zip(expected) {
+            assert!((chance - ways / 36.0).abs() < 0.000_001);
+        }
+    }
+
+    #[test]
+    fn test_pick_range_verbose() {
+        // With 41 numbers, the table shows them in groups of 3.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                assert_eq!(t.rows.len(), 14);
+                assert_eq!(t.rows[0].cells[0], ui::Cell::Text("-10 – -8"));
+                assert_eq!(t.rows[13].cells[0], ui::Cell::Text("29 – 30"));
+                assert!(t.rows[0].chosen);
+                let ui::Cell::Float(first) = t.rows[0].cells[1] else {
+                    panic!("The chances should be floats.");
+                };
+                let ui::Cell::Float(last) = t.rows[13].cells[1] else {
+                    panic!("The chances should be floats.");
+                };
+                (first - 300.0 / 41.0).abs() < 0.001 && (last - 200.0 / 41.0).abs() < 0.001
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("-10"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+
+        let result = engine.pick_range(
+            "minutes",
+            -10.0,
+            30.0,
+            Some(1.0),
+            0,
+            config::RangeDistribution::Uniform,
+        );
+
+        assert_eq!(result.unwrap(), "-10");
+    }
+
+    #[test]
+    fn test_pick_range_normal() {
+        // The numbers within one standard deviation of the mean cover the part of the number line
+        // that is within 1.25 of it, so they should come up about 79% of the time.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().returning(|| false);
+        ui.expect_prompt_choice().returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(rand::rngs::StdRng::seed_from_u64(1));
+        let distribution = config::RangeDistribution::Normal {
+            mean: Some(2.0),
+            stddev: Some(1.0),
+        };
+
+        let picks = (0..1_000)
+            .map(|_| {
+                let pick = engine
+                    .pick_range("number", -10.0, 10.0, Some(0.5), 1, distribution)
+                    .unwrap();
+                pick.parse::<f64>().unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let within = picks.iter().filter(|p| (**p - 2.0).abs() <= 1.0).count();
+        assert!((740..840).contains(&within), "{}", within);
+        assert!(picks.iter().all(|p| (p * 2.0).fract() == 0.0));
+    }
+
+    #[test]
+    fn test_pick_range_invalid() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+
+        let far = config::RangeDistribution::Normal {
+            mean: Some(1000.0),
+            stddev: Some(1.0),
+        };
+        for (min, max, step, distribution) in [
+            (2.0, 1.0, None, config::RangeDistribution::Uniform),
+            (1.0, 2.0, Some(0.0), config::RangeDistribution::Uniform),
+            (0.0, 10.0, None, far),
+            (0.0, 10.0, Some(1.0), far),
+        ] {
+            match engine.pick_range("number", min, max, step, 0, distribution) {
+                Ok(_) => {
+                    panic!("Picking from an invalid range should have returned an error.");
+                }
+                Err(error) => {
+                    assert_eq!(
+                        format!("{}", error),
+                        "The category `number` must have a minimum that isn't larger than its \
+                         maximum, a step and standard deviation that are larger than 0, and a mean \
+                         that is near enough to the range to reach it."
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_step_decimals() {
+        assert_eq!(step_decimals(1.0), 0);
+        assert_eq!(step_decimals(0.5), 1);
+        assert_eq!(step_decimals(0.25), 2);
+        assert_eq!(step_decimals(15.0), 0);
+    }
+
+    #[test]
+    fn test_pick_even() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+
+        let result = engine.pick_even(&choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![0]);
+    }
+
+    #[test]
+    fn test_pick_gaussian() {
+        let mut ui = MockUi::default();
+        // The user says no to "this", and then the chances are worked out again for the remaining
+        // candidates.
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["this", "that"]))
+            .times(2)
+            .returning(|c| c == "that");
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+
+        let result = engine.pick_gaussian(&mut choices, &[true; 3], 3.0, None, None, 1);
+
+        assert_eq!(result, Some(vec![2]));
+        assert_eq!(
+            choices,
+            vec![
+                config::Choice::from("this"),
+                config::Choice::from("the other"),
+                config::Choice::from("that"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pick_gaussian_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Text(""),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Std Devs"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Decimal(0.5),
+                                ui::Cell::Float(68.454),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Decimal(1.5),
+                                ui::Cell::Float(27.255),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Decimal(2.5),
+                                ui::Cell::Float(4.292),
+                            ],
+                            chosen: false,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+
+        let result = engine.pick_gaussian(&mut choices, &[true; 3], 3.0, None, None, 1);
+
+        assert_eq!(result, Some(vec![2]));
+        assert_eq!(
+            choices,
+            vec![
+                config::Choice::from("that"),
+                config::Choice::from("the other"),
+                config::Choice::from("this"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gaussian_chances() {
+        let chances = gaussian_chances(3, 0.0, 1.0);
+
+        assert!(abs_diff_eq!(chances[0], 0.684_537_6, epsilon = 1e-6));
+        assert!(abs_diff_eq!(chances[1], 0.272_546_1, epsilon = 1e-6));
+        assert!(abs_diff_eq!(chances[2], 0.042_916_3, epsilon = 1e-6));
+        // Long lists with small standard deviations still add up to 1, even though the chances at
+        // the end of the list are tiny.
+        let chances = gaussian_chances(100, 0.0, 2.0);
+        assert!(abs_diff_eq!(
+            chances.iter().sum::<f64>(),
+            1.0,
+            epsilon = 1e-9
+        ));
+        assert!(chances.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(gaussian_chances(1, 0.0, 0.1), vec![1.0]);
+        // A mean in the middle of the list favors the middle of the list evenly on both sides.
+        let chances = gaussian_chances(3, 1.5, 1.0);
+        assert!(chances[1] > chances[0]);
+        assert!(abs_diff_eq!(chances[0], chances[2], epsilon = 1e-9));
+        assert!(abs_diff_eq!(
+            chances.iter().sum::<f64>(),
+            1.0,
+            epsilon = 1e-9
+        ));
+    }
+
+    #[test]
+    fn test_gaussian_stddev() {
+        let stddev = gaussian_stddev(10, 0.0, 0.01);
+        assert!(abs_diff_eq!(
+            gaussian_chances(10, 0.0, stddev)[9],
+            0.01,
+            epsilon = 1e-9
+        ));
+        let stddev = gaussian_stddev(5, 2.5, 0.1);
+        assert!(abs_diff_eq!(
+            gaussian_chances(5, 2.5, stddev)[4],
+            0.1,
+            epsilon = 1e-9
+        ));
+        // The last item can't be more likely than the others when the mean is at the beginning,
+        // so the closest we can get is an even spread.
+        let stddev = gaussian_stddev(4, 0.0, 0.5);
+        assert!(
+            gaussian_chances(4, 0.0, stddev)
+                .iter()
+                .all(|c| abs_diff_eq!(*c, 0.25, epsilon = 0.001))
+        );
+    }
+
+    #[test]
+    /// Test pick_gaussian() with a mean and a last item probability, with the verbose flag set
+    fn test_pick_gaussian_mean_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                // The mean is in the middle of "that", and the standard deviation was chosen to
+                // give "the other" a 10% chance, so "this" has the same chance.
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Text(""),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Std Devs"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Decimal(-2.563),
+                                ui::Cell::Float(10.0),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Decimal(0.0),
+                                ui::Cell::Float(80.0),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Decimal(2.563),
+                                ui::Cell::Float(10.0),
+                            ],
+                            chosen: false,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+
+        let result = engine.pick_gaussian(&mut choices, &[true; 3], 3.0, Some(1.5), Some(0.1), 1);
+
+        assert_eq!(result, Some(vec![2]));
+        assert_eq!(
+            choices,
+            vec![
+                config::Choice::from("that"),
+                config::Choice::from("the other"),
+                config::Choice::from("this"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pick_gaussian_invalid() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+
+        for parameters in [
+            "stddev_scaling_factor: 0",
+            "stddev_scaling_factor: -1",
+            "mean: .nan",
+            "mean: .inf",
+            "last_item_probability: 0",
+            "last_item_probability: 1",
+        ] {
+            let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+                &format!("things:\n  model: gaussian\n  {parameters}\n  choices: [this, that]\n"),
+            )
+            .unwrap();
+
+            for result in [
+                engine.pick(&mut config, "things"),
+                engine
+                    .order(&mut config, "things", false)
+                    .map(|o| o.join(", ")),
+            ] {
+                match result {
+                    Ok(_) => {
+                        panic!("Picking with {parameters} should have returned an error.");
+                    }
+                    Err(error) => {
+                        assert_eq!(
+                            format!("{}", error),
+                            "The category `things` must have a stddev_scaling_factor that is \
+                             larger than 0, a finite mean, and a last_item_probability between 0 \
+                             and 1."
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_inventory() {
+        let mut ui = MockUi::default();
+        let mut counter = 0;
+        ui.expect_call_display_table().times(4).returning(|| false);
+        ui.expect_info()
+            .times(1)
+            .with(predicate::eq("🤨"))
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .times(4)
+            .with(predicate::in_iter(vec!["that", "the other"]))
+            .returning(move |_| {
+                if counter == 3 {
+                    true
+                } else {
+                    counter += 1;
+                    false
+                }
+            });
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::InventoryChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 0,
+            },
+            config::InventoryChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 2,
+            },
+            config::InventoryChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 3,
+            },
+        ];
+
+        let result = engine.pick_inventory(&mut choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![2]);
+        assert_eq!(
+            choices,
+            vec![
+                config::InventoryChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 0
+                },
+                config::InventoryChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 2
+                },
+                config::InventoryChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 2
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pick_inventory_many() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["that", "the other"]))
+            .times(2)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::InventoryChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 0,
+            },
+            config::InventoryChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 2,
+            },
+            config::InventoryChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 3,
+            },
+        ];
+
+        let result = engine.pick_inventory(&mut choices, &[true; 3], 2);
+
+        assert_eq!(result, vec![1, 2]);
+        assert_eq!(
+            choices,
+            vec![
+                config::InventoryChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 0
+                },
+                config::InventoryChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 1
+                },
+                config::InventoryChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 2
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pick_inventory_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Unsigned(5),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Unsigned(2),
+                                ui::Cell::Float(40.0),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Unsigned(3),
+                                ui::Cell::Float(60.0),
+                            ],
+                            chosen: false,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("that"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::InventoryChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 0,
+            },
+            config::InventoryChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 2,
+            },
+            config::InventoryChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                tickets: 3,
+            },
+        ];
+
+        let result = engine.pick_inventory(&mut choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![1]);
+        assert_eq!(
+            choices,
+            vec![
+                config::InventoryChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 0
+                },
+                config::InventoryChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 1
+                },
+                config::InventoryChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    tickets: 3
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pick_loot_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let row = |name, table, weight, chance, chosen| ui::Row {
+                    cells: vec![
+                        ui::Cell::Text(name),
+                        ui::Cell::Text(table),
+                        ui::Cell::Decimal(weight),
+                        ui::Cell::Float(chance),
+                    ],
+                    chosen,
+                };
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Text(""),
+                        ui::Cell::Decimal(1.0),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Table"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        row("Roll twice", "", 0.1, 10.0, false),
+                        row("Torch", "Common", 0.15, 15.0, false),
+                        row("Sword", "Rare → Weapons", 0.3, 30.0, false),
+                        row("Potion", "Common", 0.45, 45.0, true),
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("Potion"))
+            .times(1)
+            .returning(|_| true);
+        ui.expect_info()
+            .with(predicate::eq("Loot: Coins, Potion"))
+            .times(1)
+            .return_const(());
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+chest:
+  model: loot
+  guaranteed: [Coins]
+  choices:
+    - name: Common
+      weight: 6
+      table:
+        - name: Potion
+          weight: 3
+        - name: Torch
+    - name: Rare
+      weight: 3
+      table:
+        - name: Weapons
+          table:
+            - name: Sword
+    - name: Roll twice
+      reroll: 2
+",
+        )
+        .unwrap();
+
+        let drops = engine.roll_loot(&mut config, "chest").unwrap();
+
+        assert_eq!(drops, vec!["Coins", "Potion"]);
+    }
+
+    #[test]
+    fn test_pick_loot_reroll() {
+        // The first roll lands on "Roll twice", and the two rolls it adds can't land on it again.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(3).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["Roll twice", "Gem"]))
+            .times(3)
+            .returning(|_| true);
+        ui.expect_info()
+            .with(predicate::eq("Loot: Gem, Gem"))
+            .times(1)
+            .return_const(());
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+chest:
+  model: loot
+  choices:
+    - name: Roll twice
+      weight: 100
+      reroll: 2
+    - name: Gem
+",
+        )
+        .unwrap();
+
+        let choice = engine.pick(&mut config, "chest").unwrap();
+
+        assert_eq!(choice, "Gem, Gem");
+    }
+
+    #[test]
+    fn test_pick_loot_cooldown() {
+        // The roll lands on the Gem in the Pouch's table, so the Pouch starts cooling down.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::eq("Gem"))
+            .times(1)
+            .returning(|_| true);
+        ui.expect_info()
+            .with(predicate::eq("Loot: Gem"))
+            .times(1)
+            .return_const(());
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+chest:
+  model: loot
+  cooldown:
+    picks: 1
+  choices:
+    - name: Pouch
+      table:
+        - name: Gem
+    - name: Sword
+",
+        )
+        .unwrap();
+
+        let choice = engine.pick(&mut config, "chest").unwrap();
+
+        assert_eq!(choice, "Gem");
+        let cooling = category_choices(&config["chest"])
+            .into_iter()
+            .map(|c| c.options().cooling.as_ref().map(|c| c.picks))
+            .collect::<Vec<_>>();
+        assert_eq!(cooling, vec![Some(1), None]);
+    }
+
+    #[test]
+    fn test_pick_loot_nothing_to_roll() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+chest:
+  model: loot
+  guaranteed: [Coins]
+  choices:
+    - name: Gem
+      weight: 0
+",
+        )
+        .unwrap();
+
+        match engine.roll_loot(&mut config, "chest") {
+            Ok(_) => {
+                panic!("Rolling a table with no weight should have returned an error.");
+            }
+            Err(error) => {
+                assert_eq!(
+                    format!("{}", error),
+                    "Cannot pick 1 distinct choices from the category `chest`, which only has 0 \
+                     available."
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_loot_only_guaranteed() {
+        let mut ui = MockUi::default();
+        ui.expect_info()
+            .with(predicate::eq("Loot: Coins"))
+            .times(1)
+            .return_const(());
+        let mut engine = Engine::new(&ui);
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+chest:
+  model: loot
+  rolls: 0
+  guaranteed: [Coins]
+  choices: []
+",
+        )
+        .unwrap();
+
+        let drops = engine.roll_loot(&mut config, "chest").unwrap();
+
+        assert_eq!(drops, vec!["Coins"]);
+    }
+
+    #[test]
+    fn test_pick_loot_too_many_rolls() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+
+        for config in [
+            "
+chest:
+  model: loot
+  rolls: 4000000000
+  choices:
+    - name: Gem
+",
+            "
+chest:
+  model: loot
+  choices:
+    - name: Rare
+      table:
+        - name: Roll again
+          reroll: 1001
+        - name: Gem
+",
+        ] {
+            let mut config: BTreeMap<String, config::ConfigCategory> =
+                serde_yaml::from_str(config).unwrap();
+
+            match engine.roll_loot(&mut config, "chest") {
+                Ok(_) => {
+                    panic!("Rolling that many times should have returned an error.");
+                }
+                Err(error) => {
+                    assert_eq!(
+                        format!("{}", error),
+                        "The category `chest` must roll at most 1000 times, and its choices must \
+                         reroll at most 1000 times."
+                    );
+                }
+            }
+        }
+    }
+
+    /// Without an exponent, the lfu model should offer the choice with the lowest count, and then
+    /// the next lowest if the user says no.
+    #[test]
+    fn test_pick_lfu() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["that", "the other"]))
+            .times(2)
+            .returning(|option| option == "the other");
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::LfuChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 2.0,
+            },
+            config::LfuChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 0.0,
+            },
+            config::LfuChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 1.0,
+            },
+        ];
+
+        let result = engine.pick_lfu(&mut choices, &[true; 3], None, None, None, 1);
+
+        assert_eq!(result, vec![2]);
+        assert_eq!(
+            choices.iter().map(|c| c.count).collect::<Vec<_>>(),
+            vec![2.0, 0.0, 2.0]
+        );
+    }
+
+    /// Decay should be applied before each accepted choice's count is increased, and reaching
+    /// `reset_at` should reset every count.
+    #[test]
+    fn test_pick_lfu_decay_reset() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["this", "that"]))
+            .times(2)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::LfuChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 2.0,
+            },
+            config::LfuChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 2.5,
+            },
+            config::LfuChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 4.0,
+            },
+        ];
+
+        let result = engine.pick_lfu(&mut choices, &[true; 3], None, Some(0.5), Some(2.0), 2);
+
+        // "this" decays to 1 and goes up to 2, which resets every count. The second pick then
+        // decays every count to 0 and increases "that" to 1.
+        assert_eq!(result, vec![0, 1]);
+        assert_eq!(
+            choices.iter().map(|c| c.count).collect::<Vec<_>>(),
+            vec![0.0, 1.0, 0.0]
+        );
+    }
+
+    /// Decay and `reset_at` should leave the counts of choices that aren't eligible alone.
+    #[test]
+    fn test_pick_lfu_decay_reset_ineligible() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["this", "that"]))
+            .times(2)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::LfuChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 2.0,
+            },
+            config::LfuChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 2.5,
+            },
+            config::LfuChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 4.0,
+            },
+        ];
+
+        let result = engine.pick_lfu(
+            &mut choices,
+            &[true, true, false],
+            None,
+            Some(0.5),
+            Some(2.0),
+            2,
+        );
+
+        assert_eq!(result, vec![0, 1]);
+        assert_eq!(
+            choices.iter().map(|c| c.count).collect::<Vec<_>>(),
+            vec![0.0, 1.0, 4.0]
+        );
+    }
+
+    /// A negative count should be weighed as if it were 0, rather than giving a weight that can't
+    /// be picked from.
+    #[test]
+    fn test_pick_lfu_negative_count() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["this", "that"]))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::LfuChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: -3.0,
+            },
+            config::LfuChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: -1.0,
+            },
+        ];
+
+        let result = engine.pick_lfu(&mut choices, &[true; 2], Some(1.5), None, None, 1);
+
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_pick_lfu_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Text(""),
+                        ui::Cell::Decimal(1.75),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Count"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Decimal(3.0),
+                                ui::Cell::Decimal(0.25),
+                                ui::Cell::Float(14.286),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Decimal(0.5),
+                                ui::Cell::Float(28.571),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Decimal(0.0),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Float(57.143),
+                            ],
+                            chosen: true,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::LfuChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 0.0,
+            },
+            config::LfuChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 1.0,
+            },
+            config::LfuChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                count: 3.0,
+            },
+        ];
+
+        let result = engine.pick_lfu(&mut choices, &[true; 3], Some(1.0), None, None, 1);
+
+        assert_eq!(result, vec![0]);
+        assert_eq!(
+            choices.iter().map(|c| c.count).collect::<Vec<_>>(),
+            vec![1.0, 1.0, 3.0]
+        );
+    }
+
+    #[test]
+    fn test_pick_lru() {
+        // The user says no to the first one and yes to the second.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["this", "that"]))
+            .times(2)
+            .returning(|option| option == "that");
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+
+        let result = engine.pick_lru(&mut choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![2]);
+        assert_eq!(
+            choices,
+            vec![
+                config::Choice::from("this"),
+                config::Choice::from("the other"),
+                config::Choice::from("that")
+            ]
+        );
+    }
+
+    #[test]
+    /// Test pick_lru() with the verbose flag set
+    fn test_pick_lru_verbose() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![],
+                    header: vec![ui::Cell::Text("Name")],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![ui::Cell::Text("the other")],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![ui::Cell::Text("that")],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![ui::Cell::Text("this")],
+                            chosen: true,
+                        },
+                    ],
+                };
+                *t == expected_table
+            })
+            .times(1)
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::Choice::from("this"),
+            config::Choice::from("that"),
+            config::Choice::from("the other"),
+        ];
+
+        let result = engine.pick_lru(&mut choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![2]);
+        assert_eq!(
+            choices,
+            vec![
+                config::Choice::from("that"),
+                config::Choice::from("the other"),
+                config::Choice::from("this")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_pick_lottery() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::LotteryChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 1,
+                weight: 1,
+            },
+            config::LotteryChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 2,
+                weight: 4,
+            },
+            config::LotteryChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 3,
+                weight: 9,
+            },
+        ];
+
+        let result = engine.pick_lottery(&mut choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![0]);
+        assert_eq!(
+            choices,
+            vec![
+                config::LotteryChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 0,
+                    tickets: 0,
+                    weight: 1
+                },
+                config::LotteryChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 0,
+                    tickets: 6,
+                    weight: 4
+                },
+                config::LotteryChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 0,
+                    tickets: 12,
+                    weight: 9
+                }
+            ]
+        );
+    }
+
+    /// Each pick should run its own round of the lottery, in the order the choices were picked.
+    #[test]
+    fn test_pick_lottery_many() {
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(2).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["this", "that"]))
+            .times(2)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::LotteryChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 1,
+                weight: 1,
+            },
+            config::LotteryChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 1,
+                tickets: 2,
+                weight: 4,
+            },
+            config::LotteryChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 3,
+                weight: 9,
+            },
+        ];
+
+        let result = engine.pick_lottery(&mut choices, &[true; 3], 2);
+
+        assert_eq!(result, vec![0, 1]);
+        assert_eq!(
+            choices,
+            vec![
+                config::LotteryChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 0,
+                    tickets: 1,
+                    weight: 1
+                },
+                config::LotteryChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 1,
+                    tickets: 1,
+                    weight: 4
+                },
+                config::LotteryChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 0,
+                    tickets: 21,
+                    weight: 9
+                }
+            ]
+        );
+    }
+
+    /// If the user says no to all the choices, rpick should print out an emoji and start over.
+    /// There was previously a bug where the pick would loop forever if one of the options had 0
+    /// chance of being picked.
+    #[test]
+    fn test_pick_lottery_no_to_all_one_no_chance() {
+        let mut ui = MockUi::default();
+        let mut counter = 0;
+        ui.expect_call_display_table().times(4).returning(|| false);
+        ui.expect_info()
+            .times(1)
+            .with(predicate::eq("🤨"))
+            .returning(|_| ());
+        ui.expect_prompt_choice()
+            .times(4)
+            .with(predicate::in_iter(vec!["that", "the other"]))
+            .returning(move |_| {
+                if counter == 3 {
+                    true
+                } else {
+                    counter += 1;
+                    false
+                }
+            });
+        let mut engine = Engine::new(&ui);
+        engine.set_rng(FakeRng(0));
+        let mut choices = vec![
+            config::LotteryChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 0,
+                weight: 1,
+            },
+            config::LotteryChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 2,
+                weight: 4,
+            },
+            config::LotteryChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 3,
+                weight: 9,
+            },
+        ];
+
+        let result = engine.pick_lottery(&mut choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![2]);
+        assert_eq!(
+            choices,
+            vec![
+                config::LotteryChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 0,
+                    tickets: 1,
+                    weight: 1
+                },
+                config::LotteryChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 0,
+                    tickets: 6,
+                    weight: 4
+                },
+                config::LotteryChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 0,
+                    tickets: 0,
+                    weight: 9
+                }
+            ]
+        );
     }
 
-    // Unfortunately, the FakeRng we wrote above causes the Gaussian distribution to often
-    // pick outside of the distribution for 32-bit values on 64-bit systems. Since it is a
-    // u32, this means that the user saying no here will make the implementation loop forever
-    // until it hits MAXINT on 64-bit systems. If we made the FakeRng be a 64 bit value, then
-    // the test results on 32-bit systems would overflow. Ideally we'd have a better way than
-    // the below to get consistent test results between 32-bit and 64-bit systems, but for now
-    // we'll just skip this test on 32-bit systems.
-    #[cfg(target_pointer_width = "64")]
     #[test]
-    fn test_pick_gaussian() {
+    fn test_pick_lottery_non_zero_reset() {
         let mut ui = MockUi::default();
         ui.expect_call_display_table().times(1).returning(|| false);
         ui.expect_prompt_choice()
-            .with(predicate::eq("the other"))
+            .with(predicate::eq("this"))
             .times(1)
             .returning(|_| true);
         let mut engine = Engine::new(&ui);
-        engine.set_rng(rand::rngs::SmallRng::seed_from_u64(555));
+        engine.set_rng(FakeRng(0));
         let mut choices = vec![
-            String::from("this"),
-            String::from("that"),
-            String::from("the other"),
+            config::LotteryChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 1,
+                tickets: 1,
+                weight: 1,
+            },
+            config::LotteryChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 2,
+                tickets: 2,
+                weight: 4,
+            },
+            config::LotteryChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 3,
+                tickets: 3,
+                weight: 9,
+            },
         ];
 
-        let result = engine.pick_gaussian(&mut choices, 3.0);
+        let result = engine.pick_lottery(&mut choices, &[true; 3], 1);
 
-        assert_eq!(result, "the other");
+        assert_eq!(result, vec![0]);
         assert_eq!(
             choices,
             vec![
-                String::from("this"),
-                String::from("that"),
-                String::from("the other"),
+                config::LotteryChoice {
+                    name: "this".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 1,
+                    tickets: 1,
+                    weight: 1
+                },
+                config::LotteryChoice {
+                    name: "that".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 2,
+                    tickets: 6,
+                    weight: 4
+                },
+                config::LotteryChoice {
+                    name: "the other".to_string(),
+                    options: config::ChoiceOptions::default(),
+                    reset: 3,
+                    tickets: 12,
+                    weight: 9
+                }
             ]
         );
     }
 
-    // Unfortunately, the FakeRng we wrote above causes the Gaussian distribution to often
-    // pick outside of the distribution for 32-bit values on 64-bit systems. Since it is a
-    // u32, this means that the user saying no here will make the implementation loop forever
-    // until it hits MAXINT on 64-bit systems. If we made the FakeRng be a 64 bit value, then
-    // the test results on 32-bit systems would overflow. Ideally we'd have a better way than
-    // the below to get consistent test results between 32-bit and 64-bit systems, but for now
-    // we'll just skip this test on 32-bit systems.
-    #[cfg(target_pointer_width = "64")]
     #[test]
-    fn test_pick_gaussian_verbose() {
+    fn test_pick_lottery_ineligible() {
+        // Only "that" is eligible, so "this" and "the other" must not receive any tickets.
         let mut ui = MockUi::default();
-        ui.expect_call_display_table().times(1).returning(|| true);
-        ui.expect_display_table()
-            .withf(|t| {
-                let expected_table = ui::Table {
-                    footer: vec![ui::Cell::Text("Total"), ui::Cell::Float(99.73)],
-                    header: vec![ui::Cell::Text("Name"), ui::Cell::Text("Chance")],
-                    rows: vec![
-                        ui::Row {
-                            cells: vec![ui::Cell::Text("this"), ui::Cell::Float(68.269)],
-                            chosen: false,
-                        },
-                        ui::Row {
-                            cells: vec![ui::Cell::Text("that"), ui::Cell::Float(27.181)],
-                            chosen: false,
-                        },
-                        ui::Row {
-                            cells: vec![ui::Cell::Text("the other"), ui::Cell::Float(4.280)],
-                            chosen: true,
-                        },
-                    ],
-                };
-                tables_equal(t, &expected_table)
-            })
-            .times(1)
-            .returning(|_| ());
+        ui.expect_call_display_table().times(1).returning(|| false);
         ui.expect_prompt_choice()
-            .with(predicate::eq("the other"))
+            .with(predicate::eq("that"))
             .times(1)
             .returning(|_| true);
         let mut engine = Engine::new(&ui);
-        engine.set_rng(rand::rngs::SmallRng::seed_from_u64(555));
+        engine.set_rng(FakeRng(0));
         let mut choices = vec![
-            String::from("this"),
-            String::from("that"),
-            String::from("the other"),
+            config::LotteryChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 1,
+                weight: 1,
+            },
+            config::LotteryChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 1,
+                tickets: 2,
+                weight: 4,
+            },
+            config::LotteryChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                reset: 0,
+                tickets: 3,
+                weight: 9,
+            },
         ];
 
-        let result = engine.pick_gaussian(&mut choices, 3.0);
+        let result = engine.pick_lottery(&mut choices, &[false, true, false], 1);
 
-        assert_eq!(result, "the other");
+        assert_eq!(result, vec![1]);
         assert_eq!(
-            choices,
-            vec![
-                String::from("this"),
-                String::from("that"),
-                String::from("the other"),
-            ]
+            choices.iter().map(|c| c.tickets).collect::<Vec<_>>(),
+            vec![1, 1, 3]
         );
     }
 
     #[test]
-    fn test_pick_inventory() {
+    fn test_pick_markov() {
+        // The last choice doesn't exist anymore, so the choices' own weights are used for the
+        // first pick. "the other" may only follow "this", so it is offered second.
         let mut ui = MockUi::default();
-        let mut counter = 0;
-        ui.expect_call_display_table().times(4).returning(|| false);
-        ui.expect_info()
-            .times(1)
-            .with(predicate::eq("🤨"))
-            .returning(|_| ());
+        ui.expect_call_display_table().times(2).returning(|| false);
         ui.expect_prompt_choice()
-            .times(4)
-            .with(predicate::in_iter(vec!["that", "the other"]))
-            .returning(move |_| {
-                if counter == 3 {
-                    true
-                } else {
-                    counter += 1;
-                    false
-                }
-            });
+            .with(predicate::in_iter(vec!["this", "the other"]))
+            .times(2)
+            .returning(|_| true);
         let mut engine = Engine::new(&ui);
-        engine.set_rng(FakeRng(0));
-        let mut choices = vec![
-            config::InventoryChoice {
+        let choices = vec![
+            config::MarkovChoice {
                 name: "this".to_string(),
-                tickets: 0,
+                options: config::ChoiceOptions::default(),
+                weight: 1,
+                transitions: BTreeMap::from([
+                    ("that".to_string(), 0),
+                    ("the other".to_string(), 1),
+                ]),
             },
-            config::InventoryChoice {
+            config::MarkovChoice {
                 name: "that".to_string(),
-                tickets: 2,
+                options: config::ChoiceOptions::default(),
+                weight: 0,
+                transitions: BTreeMap::new(),
             },
-            config::InventoryChoice {
+            config::MarkovChoice {
                 name: "the other".to_string(),
-                tickets: 3,
+                options: config::ChoiceOptions::default(),
+                weight: 0,
+                transitions: BTreeMap::new(),
             },
         ];
+        let mut last = Some("gone".to_string());
 
-        let result = engine.pick_inventory(&mut choices);
+        let result = engine.pick_markov(&choices, &mut last, &[true; 3], 2);
 
-        assert_eq!(result, "the other");
-        assert_eq!(
-            choices,
-            vec![
-                config::InventoryChoice {
-                    name: "this".to_string(),
-                    tickets: 0
-                },
-                config::InventoryChoice {
-                    name: "that".to_string(),
-                    tickets: 2
-                },
-                config::InventoryChoice {
-                    name: "the other".to_string(),
-                    tickets: 2
-                }
-            ]
-        );
+        assert_eq!(result, vec![0, 2]);
+        assert_eq!(last, Some("the other".to_string()));
     }
 
     #[test]
-    fn test_pick_inventory_verbose() {
+    fn test_pick_markov_verbose() {
         let mut ui = MockUi::default();
         ui.expect_call_display_table().times(1).returning(|| true);
         ui.expect_display_table()
@@ -702,11 +8240,13 @@ mod tests {
                 let expected_table = ui::Table {
                     footer: vec![
                         ui::Cell::Text("Total"),
-                        ui::Cell::Unsigned(5),
+                        ui::Cell::Text(""),
+                        ui::Cell::Unsigned(4),
                         ui::Cell::Float(100.0),
                     ],
                     header: vec![
                         ui::Cell::Text("Name"),
+                        ui::Cell::Text("From"),
                         ui::Cell::Text("Weight"),
                         ui::Cell::Text("Chance"),
                     ],
@@ -714,16 +8254,18 @@ mod tests {
                         ui::Row {
                             cells: vec![
                                 ui::Cell::Text("that"),
-                                ui::Cell::Unsigned(2),
-                                ui::Cell::Float(40.0),
+                                ui::Cell::Text("this"),
+                                ui::Cell::Unsigned(1),
+                                ui::Cell::Float(25.0),
                             ],
                             chosen: true,
                         },
                         ui::Row {
                             cells: vec![
                                 ui::Cell::Text("the other"),
+                                ui::Cell::Text("this"),
                                 ui::Cell::Unsigned(3),
-                                ui::Cell::Float(60.0),
+                                ui::Cell::Float(75.0),
                             ],
                             chosen: false,
                         },
@@ -739,99 +8281,163 @@ mod tests {
             .returning(|_| true);
         let mut engine = Engine::new(&ui);
         engine.set_rng(FakeRng(0));
-        let mut choices = vec![
-            config::InventoryChoice {
+        let choices = vec![
+            config::MarkovChoice {
                 name: "this".to_string(),
-                tickets: 0,
+                options: config::ChoiceOptions::default(),
+                weight: 1,
+                transitions: BTreeMap::from([
+                    ("this".to_string(), 0),
+                    ("the other".to_string(), 3),
+                ]),
             },
-            config::InventoryChoice {
+            config::MarkovChoice {
                 name: "that".to_string(),
-                tickets: 2,
+                options: config::ChoiceOptions::default(),
+                weight: 1,
+                transitions: BTreeMap::new(),
             },
-            config::InventoryChoice {
+            config::MarkovChoice {
                 name: "the other".to_string(),
-                tickets: 3,
+                options: config::ChoiceOptions::default(),
+                weight: 1,
+                transitions: BTreeMap::new(),
             },
         ];
+        let mut last = Some("this".to_string());
 
-        let result = engine.pick_inventory(&mut choices);
+        let result = engine.pick_markov(&choices, &mut last, &[true; 3], 1);
 
-        assert_eq!(result, "that");
-        assert_eq!(
-            choices,
-            vec![
-                config::InventoryChoice {
-                    name: "this".to_string(),
-                    tickets: 0
-                },
-                config::InventoryChoice {
-                    name: "that".to_string(),
-                    tickets: 1
-                },
-                config::InventoryChoice {
-                    name: "the other".to_string(),
-                    tickets: 3
-                }
-            ]
-        );
+        assert_eq!(result, vec![1]);
+        assert_eq!(last, Some("that".to_string()));
     }
 
     #[test]
-    fn test_pick_lru() {
-        // The user says no to the first one and yes to the second.
+    fn test_pick_quota() {
+        // "that" is furthest behind its target, so it is offered first. The user rejects it, so
+        // "this" is offered next.
         let mut ui = MockUi::default();
+        let mut answers = vec![true, false];
         ui.expect_call_display_table().times(2).returning(|| false);
         ui.expect_prompt_choice()
-            .with(predicate::in_iter(vec!["this", "that"]))
+            .with(predicate::in_iter(vec!["that", "this"]))
             .times(2)
-            .returning(|option| option == "that");
+            .returning(move |_| answers.pop().unwrap());
         let mut engine = Engine::new(&ui);
-        engine.set_rng(FakeRng(0));
         let mut choices = vec![
-            String::from("this"),
-            String::from("that"),
-            String::from("the other"),
+            config::QuotaChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                target: 2,
+                picks: 2,
+            },
+            config::QuotaChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                target: 3,
+                picks: 1,
+            },
+            config::QuotaChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                target: 0,
+                picks: 1,
+            },
         ];
 
-        let result = engine.pick_lru(&mut choices);
+        let result = engine.pick_quota(&mut choices, &[true; 3], 0.0, 1);
 
-        assert_eq!(result, "that");
+        assert_eq!(result, vec![0]);
         assert_eq!(
-            choices,
-            vec![
-                String::from("this"),
-                String::from("the other"),
-                String::from("that")
-            ]
+            choices.iter().map(|c| c.picks).collect::<Vec<_>>(),
+            vec![3, 1, 1]
         );
     }
 
     #[test]
-    /// Test pick_lru() with the verbose flag set
-    fn test_pick_lru_verbose() {
+    fn test_pick_quota_invalid_tolerance() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+
+        for tolerance in ["-1", ".nan", ".inf"] {
+            let mut config: BTreeMap<String, config::ConfigCategory> =
+                serde_yaml::from_str(&format!(
+                    "chores:\n  model: quota\n  tolerance: {tolerance}\n  choices:\n    - name: \
+                     dishes\n      target: 1\n"
+                ))
+                .unwrap();
+
+            match engine.pick(&mut config, "chores") {
+                Ok(_) => {
+                    panic!("A tolerance of {tolerance} should have returned an error.");
+                }
+                Err(error) => {
+                    assert_eq!(
+                        format!("{}", error),
+                        "The category `chores` must have a tolerance that is a finite number of \
+                         at least 0."
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pick_quota_verbose() {
+        // "this" and "the other" are within the tolerance of each other, so they are tied.
         let mut ui = MockUi::default();
         ui.expect_call_display_table().times(1).returning(|| true);
         ui.expect_display_table()
             .withf(|t| {
                 let expected_table = ui::Table {
-                    footer: vec![],
-                    header: vec![ui::Cell::Text("Name")],
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Text(""),
+                        ui::Cell::Text(""),
+                        ui::Cell::Text(""),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Target"),
+                        ui::Cell::Text("Actual"),
+                        ui::Cell::Text("Deficit"),
+                        ui::Cell::Text("Chance"),
+                    ],
                     rows: vec![
                         ui::Row {
-                            cells: vec![ui::Cell::Text("the other")],
-                            chosen: false,
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Float(25.0),
+                                ui::Cell::Float(0.0),
+                                ui::Cell::Decimal(0.75),
+                                ui::Cell::Float(50.0),
+                            ],
+                            chosen: true,
                         },
                         ui::Row {
-                            cells: vec![ui::Cell::Text("that")],
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Float(50.0),
+                                ui::Cell::Float(50.0),
+                                ui::Cell::Decimal(0.5),
+                                ui::Cell::Float(50.0),
+                            ],
                             chosen: false,
                         },
                         ui::Row {
-                            cells: vec![ui::Cell::Text("this")],
-                            chosen: true,
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Float(25.0),
+                                ui::Cell::Float(50.0),
+                                ui::Cell::Decimal(-0.25),
+                                ui::Cell::Float(0.0),
+                            ],
+                            chosen: false,
                         },
                     ],
                 };
-                *t == expected_table
+                tables_equal(t, &expected_table)
             })
             .times(1)
             .returning(|_| ());
@@ -842,214 +8448,463 @@ mod tests {
         let mut engine = Engine::new(&ui);
         engine.set_rng(FakeRng(0));
         let mut choices = vec![
-            String::from("this"),
-            String::from("that"),
-            String::from("the other"),
+            config::QuotaChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                target: 1,
+                picks: 0,
+            },
+            config::QuotaChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                target: 1,
+                picks: 1,
+            },
+            config::QuotaChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                target: 2,
+                picks: 1,
+            },
         ];
 
-        let result = engine.pick_lru(&mut choices);
+        let result = engine.pick_quota(&mut choices, &[true; 3], 0.5, 1);
 
-        assert_eq!(result, "this");
-        assert_eq!(
-            choices,
-            vec![
-                String::from("that"),
-                String::from("the other"),
-                String::from("this")
-            ]
-        );
+        assert_eq!(result, vec![0]);
+    }
+
+    #[test]
+    fn test_pick_rated() {
+        // "this" has a rating of 0, so it should never be offered.
+        let mut ui = MockUi::default();
+        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_prompt_choice()
+            .with(predicate::in_iter(vec!["that", "the other"]))
+            .times(1)
+            .returning(|_| true);
+        let mut engine = Engine::new(&ui);
+        let choices = vec![
+            config::RatedChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                rating: 0.0,
+                comparisons: 4,
+            },
+            config::RatedChoice {
+                name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
+                rating: 1500.0,
+                comparisons: 0,
+            },
+            config::RatedChoice {
+                name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
+                rating: 1616.5,
+                comparisons: 2,
+            },
+        ];
+
+        let result = engine.pick_rated(&choices, &[true; 3], 1);
+
+        assert_ne!(result, vec![0]);
     }
 
     #[test]
-    fn test_pick_lottery() {
+    fn test_pick_recency() {
         let mut ui = MockUi::default();
-        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_call_display_table().times(3).returning(|| false);
         ui.expect_prompt_choice()
-            .with(predicate::eq("this"))
-            .times(1)
-            .returning(|_| true);
+            .with(predicate::in_iter(vec!["this", "that", "the other"]))
+            .times(3)
+            .returning(|c| c == "the other");
         let mut engine = Engine::new(&ui);
         engine.set_rng(FakeRng(0));
+        engine.set_now(DateTime::parse_from_rfc3339("2026-10-16T12:00:00-04:00").unwrap());
         let mut choices = vec![
-            config::LotteryChoice {
+            config::RecencyChoice {
                 name: "this".to_string(),
-                reset: 0,
-                tickets: 1,
-                weight: 1,
+                options: config::ChoiceOptions::default(),
+                last_picked: Some(
+                    DateTime::parse_from_rfc3339("2026-10-15T12:00:00-04:00").unwrap(),
+                ),
             },
-            config::LotteryChoice {
+            config::RecencyChoice {
                 name: "that".to_string(),
-                reset: 0,
-                tickets: 2,
-                weight: 4,
+                options: config::ChoiceOptions::default(),
+                last_picked: Some(
+                    DateTime::parse_from_rfc3339("2026-10-16T11:00:00-04:00").unwrap(),
+                ),
             },
-            config::LotteryChoice {
+            config::RecencyChoice {
                 name: "the other".to_string(),
-                reset: 0,
-                tickets: 3,
-                weight: 9,
+                options: config::ChoiceOptions::default(),
+                last_picked: None,
             },
         ];
 
-        let result = engine.pick_lottery(&mut choices);
+        let result = engine.pick_recency(&mut choices, &[true; 3], config::RecencyCurve::Linear, 1);
 
-        assert_eq!(result, "this");
+        assert_eq!(result, vec![2]);
         assert_eq!(
             choices,
             vec![
-                config::LotteryChoice {
+                config::RecencyChoice {
                     name: "this".to_string(),
-                    reset: 0,
-                    tickets: 0,
-                    weight: 1
+                    options: config::ChoiceOptions::default(),
+                    last_picked: Some(
+                        DateTime::parse_from_rfc3339("2026-10-15T12:00:00-04:00").unwrap()
+                    ),
                 },
-                config::LotteryChoice {
+                config::RecencyChoice {
                     name: "that".to_string(),
-                    reset: 0,
-                    tickets: 6,
-                    weight: 4
+                    options: config::ChoiceOptions::default(),
+                    last_picked: Some(
+                        DateTime::parse_from_rfc3339("2026-10-16T11:00:00-04:00").unwrap()
+                    ),
                 },
-                config::LotteryChoice {
+                config::RecencyChoice {
                     name: "the other".to_string(),
-                    reset: 0,
-                    tickets: 12,
-                    weight: 9
-                }
+                    options: config::ChoiceOptions::default(),
+                    last_picked: Some(
+                        DateTime::parse_from_rfc3339("2026-10-16T12:00:00-04:00").unwrap()
+                    ),
+                },
             ]
         );
     }
 
-    /// If the user says no to all the choices, rpick should print out an emoji and start over.
-    /// There was previously a bug where the pick would loop forever if one of the options had 0
-    /// chance of being picked.
     #[test]
-    fn test_pick_lottery_no_to_all_one_no_chance() {
+    fn test_pick_recency_verbose() {
         let mut ui = MockUi::default();
-        let mut counter = 0;
-        ui.expect_call_display_table().times(4).returning(|| false);
-        ui.expect_info()
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Text(""),
+                        ui::Cell::Decimal(17.0),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Days Since"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Decimal(0.0),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Float(5.882),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Decimal(3.0),
+                                ui::Cell::Decimal(8.0),
+                                ui::Cell::Float(47.059),
+                            ],
+                            chosen: true,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Text("never"),
+                                ui::Cell::Decimal(8.0),
+                                ui::Cell::Float(47.059),
+                            ],
+                            chosen: false,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
             .times(1)
-            .with(predicate::eq("🤨"))
             .returning(|_| ());
         ui.expect_prompt_choice()
-            .times(4)
-            .with(predicate::in_iter(vec!["that", "the other"]))
-            .returning(move |_| {
-                if counter == 3 {
-                    true
-                } else {
-                    counter += 1;
-                    false
-                }
-            });
+            .with(predicate::eq("this"))
+            .times(1)
+            .returning(|_| true);
         let mut engine = Engine::new(&ui);
         engine.set_rng(FakeRng(0));
+        engine.set_now(DateTime::parse_from_rfc3339("2026-10-16T12:00:00-04:00").unwrap());
         let mut choices = vec![
-            config::LotteryChoice {
+            config::RecencyChoice {
                 name: "this".to_string(),
-                reset: 0,
-                tickets: 0,
-                weight: 1,
+                options: config::ChoiceOptions::default(),
+                last_picked: Some(
+                    DateTime::parse_from_rfc3339("2026-10-13T12:00:00-04:00").unwrap(),
+                ),
             },
-            config::LotteryChoice {
+            config::RecencyChoice {
                 name: "that".to_string(),
-                reset: 0,
-                tickets: 2,
-                weight: 4,
+                options: config::ChoiceOptions::default(),
+                last_picked: Some(
+                    DateTime::parse_from_rfc3339("2026-10-16T12:00:00-04:00").unwrap(),
+                ),
             },
-            config::LotteryChoice {
+            config::RecencyChoice {
                 name: "the other".to_string(),
-                reset: 0,
-                tickets: 3,
-                weight: 9,
+                options: config::ChoiceOptions::default(),
+                last_picked: None,
             },
         ];
 
-        let result = engine.pick_lottery(&mut choices);
+        let result = engine.pick_recency(
+            &mut choices,
+            &[true; 3],
+            config::RecencyCurve::Exponential { doubling_days: 1.0 },
+            1,
+        );
 
-        assert_eq!(result, "the other");
-        assert_eq!(
-            choices,
-            vec![
-                config::LotteryChoice {
-                    name: "this".to_string(),
-                    reset: 0,
-                    tickets: 1,
-                    weight: 1
-                },
-                config::LotteryChoice {
-                    name: "that".to_string(),
-                    reset: 0,
-                    tickets: 6,
-                    weight: 4
-                },
-                config::LotteryChoice {
-                    name: "the other".to_string(),
-                    reset: 0,
-                    tickets: 0,
-                    weight: 9
+        assert_eq!(result, vec![0]);
+    }
+
+    #[test]
+    fn test_recency_weight() {
+        let capped = config::RecencyCurve::Capped { max_days: 5.0 };
+        let exponential = config::RecencyCurve::Exponential { doubling_days: 2.0 };
+
+        assert!((recency_weight(config::RecencyCurve::Linear, 0.0) - 1.0).abs() < 0.000_001);
+        assert!((recency_weight(config::RecencyCurve::Linear, 9.5) - 10.5).abs() < 0.000_001);
+        assert!((recency_weight(exponential, 0.0) - 1.0).abs() < 0.000_001);
+        assert!((recency_weight(exponential, 6.0) - 8.0).abs() < 0.000_001);
+        assert!((recency_weight(capped, 3.0) - 4.0).abs() < 0.000_001);
+        assert!((recency_weight(capped, 30.0) - 6.0).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn test_pick_recency_invalid_curve() {
+        let ui = MockUi::default();
+        let mut engine = Engine::new(&ui);
+
+        for curve in [
+            "{shape: exponential, doubling_days: 0}",
+            "{shape: exponential, doubling_days: .nan}",
+            "{shape: capped, max_days: -5}",
+        ] {
+            let mut config: BTreeMap<String, config::ConfigCategory> =
+                serde_yaml::from_str(&format!(
+                    "food:\n  model: recency\n  curve: {curve}\n  choices:\n    - name: pizza\n"
+                ))
+                .unwrap();
+
+            match engine.pick(&mut config, "food") {
+                Ok(_) => {
+                    panic!("The curve {curve} should have returned an error.");
                 }
-            ]
-        );
+                Err(error) => {
+                    assert_eq!(
+                        format!("{}", error),
+                        "The category `food` must have a doubling_days that is larger than 0, or \
+                         a max_days of at least 0."
+                    );
+                }
+            }
+        }
     }
 
     #[test]
-    fn test_pick_lottery_non_zero_reset() {
+    fn test_pick_spaced_repetition_verbose() {
         let mut ui = MockUi::default();
-        ui.expect_call_display_table().times(1).returning(|| false);
+        ui.expect_call_display_table().times(1).returning(|| true);
+        ui.expect_display_table()
+            .withf(|t| {
+                let expected_table = ui::Table {
+                    footer: vec![
+                        ui::Cell::Text("Total"),
+                        ui::Cell::Text(""),
+                        ui::Cell::Text(""),
+                        ui::Cell::Text(""),
+                        ui::Cell::Decimal(5.333),
+                        ui::Cell::Float(100.0),
+                    ],
+                    header: vec![
+                        ui::Cell::Text("Name"),
+                        ui::Cell::Text("Due"),
+                        ui::Cell::Text("Interval"),
+                        ui::Cell::Text("Ease"),
+                        ui::Cell::Text("Weight"),
+                        ui::Cell::Text("Chance"),
+                    ],
+                    rows: vec![
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("this"),
+                                ui::Cell::Text("2026-10-18 12:00"),
+                                ui::Cell::Unsigned(6),
+                                ui::Cell::Decimal(2.5),
+                                ui::Cell::Decimal(0.333),
+                                ui::Cell::Float(6.25),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("the other"),
+                                ui::Cell::Text("new"),
+                                ui::Cell::Unsigned(0),
+                                ui::Cell::Decimal(2.5),
+                                ui::Cell::Decimal(1.0),
+                                ui::Cell::Float(18.75),
+                            ],
+                            chosen: false,
+                        },
+                        ui::Row {
+                            cells: vec![
+                                ui::Cell::Text("that"),
+                                ui::Cell::Text("2026-10-13 12:00"),
+                                ui::Cell::Unsigned(1),
+                                ui::Cell::Decimal(2.36),
+                                ui::Cell::Decimal(4.0),
+                                ui::Cell::Float(75.0),
+                            ],
+                            chosen: true,
+                        },
+                    ],
+                };
+                tables_equal(t, &expected_table)
+            })
+            .times(1)
+            .returning(|_| ());
         ui.expect_prompt_choice()
-            .with(predicate::eq("this"))
+            .with(predicate::eq("that"))
             .times(1)
             .returning(|_| true);
+        ui.expect_prompt_grade()
+            .with(predicate::eq("that"))
+            .times(1)
+            .returning(|_| 4);
         let mut engine = Engine::new(&ui);
         engine.set_rng(FakeRng(0));
+        engine.set_now(DateTime::parse_from_rfc3339("2026-10-16T12:00:00-04:00").unwrap());
         let mut choices = vec![
-            config::LotteryChoice {
-                name: "this".to_string(),
-                reset: 1,
-                tickets: 1,
-                weight: 1,
-            },
-            config::LotteryChoice {
+            config::SpacedRepetitionChoice {
                 name: "that".to_string(),
-                reset: 2,
-                tickets: 2,
-                weight: 4,
+                options: config::ChoiceOptions::default(),
+                repetitions: 1,
+                interval: 1,
+                ease: 2.36,
+                due: Some(DateTime::parse_from_rfc3339("2026-10-13T12:00:00-04:00").unwrap()),
             },
-            config::LotteryChoice {
+            config::SpacedRepetitionChoice {
+                name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
+                repetitions: 2,
+                interval: 6,
+                ease: 2.5,
+                due: Some(DateTime::parse_from_rfc3339("2026-10-18T12:00:00-04:00").unwrap()),
+            },
+            config::SpacedRepetitionChoice {
                 name: "the other".to_string(),
-                reset: 3,
-                tickets: 3,
-                weight: 9,
+                options: config::ChoiceOptions::default(),
+                repetitions: 0,
+                interval: 0,
+                ease: 2.5,
+                due: None,
             },
         ];
 
-        let result = engine.pick_lottery(&mut choices);
+        let result = engine.pick_spaced_repetition(&mut choices, &[true; 3], 1);
 
-        assert_eq!(result, "this");
+        assert_eq!(result, vec![0]);
+        assert_eq!(choices[0].repetitions, 2);
+        assert_eq!(choices[0].interval, 6);
+        assert!((choices[0].ease - 2.36).abs() < 0.000_001);
         assert_eq!(
-            choices,
-            vec![
-                config::LotteryChoice {
-                    name: "this".to_string(),
-                    reset: 1,
-                    tickets: 1,
-                    weight: 1
-                },
-                config::LotteryChoice {
-                    name: "that".to_string(),
-                    reset: 2,
-                    tickets: 6,
-                    weight: 4
-                },
-                config::LotteryChoice {
-                    name: "the other".to_string(),
-                    reset: 3,
-                    tickets: 12,
-                    weight: 9
-                }
-            ]
+            choices[0].due,
+            Some(DateTime::parse_from_rfc3339("2026-10-22T12:00:00-04:00").unwrap())
         );
+        // The other choices weren't reviewed, so they shouldn't have changed.
+        assert_eq!(choices[1].interval, 6);
+        assert_eq!(choices[2].due, None);
+    }
+
+    /// A Ui that doesn't implement prompt_grade() should have a yes graded as 4.
+    #[test]
+    fn test_pick_spaced_repetition_default_grade() {
+        struct AcceptingUi;
+
+        impl ui::Ui for AcceptingUi {
+            fn call_display_table(&self) -> bool {
+                false
+            }
+
+            fn display_table(&self, _table: &ui::Table) {}
+
+            fn info(&self, _message: &str) {}
+
+            fn prompt_choice(&self, _choice: &str) -> bool {
+                true
+            }
+        }
+
+        let ui = AcceptingUi;
+        let mut engine = Engine::new(&ui);
+        let mut config: BTreeMap<String, config::ConfigCategory> = serde_yaml::from_str(
+            "
+cards:
+  model: spaced_repetition
+  choices:
+    - name: this
+",
+        )
+        .unwrap();
+
+        engine.pick(&mut config, "cards").unwrap();
+
+        let config::ConfigCategory::SpacedRepetition { choices, .. } = &config["cards"] else {
+            panic!("The category changed its model.");
+        };
+        assert_eq!(choices[0].repetitions, 1);
+        assert_eq!(choices[0].interval, 1);
+        assert_eq!(choices[0].ease, 2.5);
+    }
+
+    #[test]
+    fn test_review() {
+        let now = DateTime::parse_from_rfc3339("2026-10-16T12:00:00-04:00").unwrap();
+        let mut choice = config::SpacedRepetitionChoice {
+            name: "this".to_string(),
+            options: config::ChoiceOptions::default(),
+            repetitions: 0,
+            interval: 0,
+            ease: 2.5,
+            due: None,
+        };
+        let mut assert_review = |grade, repetitions, interval, ease: f64| {
+            review(&mut choice, grade, now);
+
+            assert_eq!(choice.repetitions, repetitions);
+            assert_eq!(choice.interval, interval);
+            assert!((choice.ease - ease).abs() < 0.000_001);
+            assert_eq!(
+                choice.due,
+                Some(now + chrono::Duration::days(interval as i64))
+            );
+        };
+
+        assert_review(5, 1, 1, 2.6);
+        assert_review(3, 2, 6, 2.46);
+        // Grades above 5 are treated as 5.
+        assert_review(9, 3, 15, 2.56);
+        // Forgetting the choice starts the repetitions over.
+        assert_review(1, 0, 1, 2.02);
+        assert_review(0, 0, 1, 1.3);
+        // The ease never falls below the minimum.
+        assert_review(0, 0, 1, 1.3);
+    }
+
+    #[test]
+    fn test_spaced_repetition_weight() {
+        assert!((spaced_repetition_weight(0.0) - 1.0).abs() < 0.000_001);
+        assert!((spaced_repetition_weight(3.5) - 4.5).abs() < 0.000_001);
+        assert!((spaced_repetition_weight(-3.0) - 0.25).abs() < 0.000_001);
     }
 
     #[test]
@@ -1065,21 +8920,24 @@ mod tests {
         let choices = vec![
             config::WeightedChoice {
                 name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
                 weight: 1,
             },
             config::WeightedChoice {
                 name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
                 weight: 4,
             },
             config::WeightedChoice {
                 name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
                 weight: 9,
             },
         ];
 
-        let result = engine.pick_weighted(&choices);
+        let result = engine.pick_weighted(&choices, &[true; 3], 1);
 
-        assert_eq!(result, "this");
+        assert_eq!(result, vec![0]);
     }
 
     /// There was a bug wherein saying no to all weighted options crashed rpick rather than
@@ -1109,21 +8967,33 @@ mod tests {
         let choices = vec![
             config::WeightedChoice {
                 name: "this".to_string(),
+                options: config::ChoiceOptions::default(),
                 weight: 1,
             },
             config::WeightedChoice {
                 name: "that".to_string(),
+                options: config::ChoiceOptions::default(),
                 weight: 4,
             },
             config::WeightedChoice {
                 name: "the other".to_string(),
+                options: config::ChoiceOptions::default(),
                 weight: 9,
             },
         ];
 
-        let result = engine.pick_weighted(&choices);
+        let result = engine.pick_weighted(&choices, &[true; 3], 1);
+
+        assert_eq!(result, vec![0]);
+    }
+
+    #[test]
+    fn test_move_to_end() {
+        let mut choices = vec!["a", "b", "c", "d", "e"];
+
+        move_to_end(&mut choices, &[3, 0, 2]);
 
-        assert_eq!(result, "this");
+        assert_eq!(choices, vec!["b", "e", "d", "a", "c"]);
     }
 
     fn tables_equal(a: &ui::Table, b: &ui::Table) -> bool {
@@ -1147,16 +9017,18 @@ mod tests {
             return false;
         }
         for (i, cell) in a.iter().enumerate() {
-            if let ui::Cell::Float(a_value) = cell {
-                if let ui::Cell::Float(b_value) = b[i] {
-                    if !abs_diff_eq!(*a_value, b_value, epsilon = 0.001) {
+            match (cell, &b[i]) {
+                (ui::Cell::Float(a_value), ui::Cell::Float(b_value))
+                | (ui::Cell::Decimal(a_value), ui::Cell::Decimal(b_value)) => {
+                    if !abs_diff_eq!(*a_value, *b_value, epsilon = 0.001) {
+                        return false;
+                    }
+                }
+                _ => {
+                    if *cell != b[i] {
                         return false;
                     }
-                } else {
-                    return false;
                 }
-            } else if *cell != b[i] {
-                return false;
             }
         }
         true
@@ -44,9 +44,8 @@ along with this program.  If not, see <http://www.gnu.org/licenses/>.*/
 //! // works as expected at the end. You most likely do not want to do this in practice as it takes
 //! // the randomness out of the system.
 //! engine.set_rng(rand::rngs::SmallRng::seed_from_u64(37));
-//! let choices = vec![String::from("this"), String::from("that"),
-//!                    String::from("the other")];
-//! let category = rpick::config::ConfigCategory::Even{choices: choices};
+//! let choices = vec!["this".into(), "that".into(), "the other".into()];
+//! let category = rpick::config::ConfigCategory::Even{choices, cooldown: None};
 //! let mut config = BTreeMap::new();
 //! config.insert("things".to_string(), category);
 //!
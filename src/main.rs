@@ -39,7 +39,30 @@ fn main() {
             let ui = Cli::new(args.verbose);
 
             let mut engine = rpick::engine::Engine::new(&ui);
-            match engine.pick(&mut config, args.category.as_ref()) {
+            engine.set_tags(args.tags.clone(), args.not_tags.clone());
+            if let Some(at) = args.at {
+                match at.and_local_timezone(chrono::Local).earliest() {
+                    Some(at) => engine.set_now(at.fixed_offset()),
+                    None => {
+                        println!("The time {} does not exist in the local time zone.", at);
+                        std::process::exit(1);
+                    }
+                }
+            }
+            let result = match args.tournament {
+                Some(rounds) => engine.run_tournament(&mut config, args.category.as_ref(), rounds),
+                None if args.order => engine
+                    .order(&mut config, args.category.as_ref(), args.update)
+                    .map(|names| {
+                        for (position, name) in names.iter().enumerate() {
+                            println!("{}. {}", position + 1, name);
+                        }
+                    }),
+                None => engine
+                    .pick_many(&mut config, args.category.as_ref(), args.count)
+                    .map(|_| ()),
+            };
+            match result {
                 Ok(_) => match rpick::config::write_config(&config_path, config) {
                     Ok(_) => {}
                     Err(error) => {
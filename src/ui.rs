@@ -16,7 +16,9 @@ along with this program.  If not, see <http://www.gnu.org/licenses/>.*/
 
 /// An individual cell within rpick's chance tables.
 ///
-/// Each of the variants expresses its contained type, and should be fairly obvious.
+/// Each of the variants expresses its contained type, and should be fairly obvious. `Float` is used
+/// for percentages, such as the chance that a candidate has of being chosen, while `Decimal` is
+/// used for any other fractional number.
 #[non_exhaustive]
 #[derive(Debug, PartialEq)]
 pub enum Cell<'a> {
@@ -25,6 +27,7 @@ pub enum Cell<'a> {
     Integer(i64),
     Float(f64),
     Unsigned(u64),
+    Decimal(f64),
 }
 
 impl From<f64> for Cell<'_> {
@@ -53,6 +56,7 @@ impl From<&Cell<'_>> for String {
             Cell::Integer(value) => value.to_string(),
             Cell::Float(value) => value.to_string(),
             Cell::Unsigned(value) => value.to_string(),
+            Cell::Decimal(value) => value.to_string(),
         }
     }
 }
@@ -98,4 +102,29 @@ pub trait Ui {
     ///
     /// Return `true` if the user accepts the choice.
     fn prompt_choice(&self, choice: &str) -> bool;
+
+    /// Ask the user which of the two given choices they prefer.
+    ///
+    /// Return `true` if the user prefers the first choice, or `false` if they prefer the second.
+    ///
+    /// The default implementation asks with [`Ui::prompt_choice`] whether the user prefers the
+    /// first choice over the second.
+    fn prompt_comparison(&self, first: &str, second: &str) -> bool {
+        self.prompt_choice(&format!("{} over {}", first, second))
+    }
+
+    /// Ask the user how well they recalled the given choice, which they have just accepted.
+    ///
+    /// Return a grade from 0, for a complete blackout, to 5, for perfect recall. Grades of 3 and
+    /// up count as correct recall.
+    ///
+    /// The default implementation asks with [`Ui::prompt_choice`] whether the user recalled the
+    /// choice, and grades a yes as 4 and a no as 1.
+    fn prompt_grade(&self, choice: &str) -> u8 {
+        if self.prompt_choice(&format!("{} recalled", choice)) {
+            4
+        } else {
+            1
+        }
+    }
 }
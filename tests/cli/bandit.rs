@@ -0,0 +1,55 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the bandit model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+bandit:
+  model: bandit
+  choices:
+    - name: option 1
+      accepted: 3
+      rejected: 1
+    - name: option 2
+    - name: option 3
+      rejected: 2
+";
+
+#[test]
+// Assert that the bandit model records both the rejection and the acceptance
+fn pick() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["bandit"], "n\ny\n", true);
+
+    let picks = super::get_picks(&stdout);
+    assert_eq!(picks.len(), 2);
+    assert_ne!(picks[0], picks[1]);
+    let mut expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
+    if let ConfigCategory::Bandit { choices, .. } = &mut expected_config.get_mut("bandit").unwrap()
+    {
+        let rejected = choices.iter().position(|x| x.name == picks[0]).unwrap();
+        choices[rejected].rejected += 1;
+        let accepted = choices.iter().position(|x| x.name == picks[1]).unwrap();
+        choices[accepted].accepted += 1;
+    }
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
@@ -0,0 +1,86 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the bundle model.
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+snacks:
+  model: bundle
+  budget: 20
+  choices:
+    - name: chips
+      cost: 3.5
+    - name: soda
+      cost: 2
+      weight: 3
+    - name: candy bar
+      cost: 1.25
+    - name: sandwich
+      cost: 8
+    - name: cake
+      cost: 25
+";
+
+#[test]
+// Assert that the bundle stays within the budget, and that rejecting a bundle draws a new one
+fn pick() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["snacks"], "n\ny\n", true);
+
+    // get_picks() stops at the decimal point, so we match the whole bundles here.
+    let re = Regex::new(r"Choice is (?P<bundle>[^(]*) \(total cost (?P<cost>[\d.]+)\)\. Accept")
+        .unwrap();
+    let bundles = re.captures_iter(&stdout).collect::<Vec<_>>();
+    assert_eq!(bundles.len(), 2);
+    for bundle in bundles {
+        let cost: f64 = bundle["cost"].parse().unwrap();
+        assert!(cost <= 20.0);
+        // Everything but the cake fits, since it adds up to 14.75.
+        assert_eq!(&bundle["bundle"], "chips, soda, candy bar, sandwich");
+        assert_eq!(&bundle["cost"], "14.75");
+    }
+    // The bundle model doesn't keep any state.
+    let expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that a budget that nothing fits in gives an error
+fn pick_nothing_fits() {
+    let config = "
+---
+snacks:
+  model: bundle
+  budget: 2
+  choices:
+    - name: cake
+      cost: 25
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["snacks"], "", false);
+
+    assert_eq!(
+        stdout,
+        "None of the choices of the category `snacks` fit within its budget.\n"
+    );
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the composite model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that the composite model never picks a choice whose factors make its weight 0, and that
+// the lru factor moves the chosen item to the end of the list
+fn pick() {
+    let config = "
+---
+dinner:
+  model: composite
+  factors:
+    - factor: weight
+    - factor: lru
+      decay: 0.5
+  choices:
+    - name: pizza
+      weight: 0
+    - name: tacos
+    - name: sushi
+      weight: 0
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["dinner"], "y\n", true);
+
+    assert_eq!(super::get_pick(&stdout), "tacos");
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+dinner:
+  model: composite
+  combine: multiply
+  factors:
+    - factor: weight
+    - factor: lru
+      decay: 0.5
+  choices:
+    - name: pizza
+      weight: 0
+    - name: sushi
+      weight: 0
+    - name: tacos
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that the verbose table breaks the weights down by factor
+fn pick_verbose() {
+    let config = "
+---
+dinner:
+  model: composite
+  combine: add
+  factors:
+    - factor: weight
+    - factor: tags
+      boosts:
+        spicy: 2.5
+  choices:
+    - name: pizza
+      weight: 3
+    - name: tacos
+      tags: [spicy]
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["dinner", "-v"], "y\n", true);
+
+    assert!(stdout.contains("Static Weight"));
+    assert!(stdout.contains("Tag Boost"));
+    assert!(stdout.contains("3.5"));
+}
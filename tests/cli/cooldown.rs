@@ -0,0 +1,160 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of cooldowns.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+even:
+  model: even
+  cooldown:
+    picks: 2
+  choices:
+    - option 1
+";
+
+#[test]
+// Assert that an accepted choice is held back for the configured number of picks
+fn pick() {
+    let config = "
+---
+weighted:
+  model: weighted
+  cooldown:
+    picks: 1
+  choices:
+    - name: option 1
+      weight: 1000000
+    - name: option 2
+      weight: 1
+";
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["weighted"], "y\n", true);
+    // This has a very small chance of failing, but the weights make that very unlikely.
+    assert_eq!(stdout, "Choice is option 1. Accept? (Y/n) ");
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+weighted:
+  model: weighted
+  cooldown:
+    picks: 1
+  choices:
+    - name: option 1
+      weight: 1000000
+      cooling:
+        picks: 1
+    - name: option 2
+      weight: 1
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+
+    // Since option 1 is cooling down, option 2 must be picked next.
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(&config_contents, &mut ["weighted"], "y\n", true);
+    assert_eq!(stdout, "Choice is option 2. Accept? (Y/n) ");
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+weighted:
+  model: weighted
+  cooldown:
+    picks: 1
+  choices:
+    - name: option 1
+      weight: 1000000
+    - name: option 2
+      weight: 1
+      cooling:
+        picks: 1
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// The user should get a useful error message if every choice is cooling down
+fn all_cooling() {
+    let (_, config_contents) = super::test_rpick_with_config(CONFIG, &mut ["even"], "y\n", true);
+
+    let (stdout, _) = super::test_rpick_with_config(&config_contents, &mut ["even"], "", false);
+
+    assert_eq!(
+        stdout,
+        "Cannot pick 1 distinct choices from the category `even`, which only has 0 available.\n"
+    );
+}
+
+#[test]
+// Assert that the choices that take part in a single outcome, such as teams, start cooling down
+fn outcome() {
+    let config = "
+---
+game:
+  model: teams
+  teams: 2
+  cooldown:
+    picks: 2
+  choices:
+    - name: Alice
+    - name: Bob
+    - name: Carol
+    - name: Dave
+    - name: Erin
+      cooling:
+        picks: 1
+";
+
+    let (_, config_contents) = super::test_rpick_with_config(config, &mut ["game"], "y\n", true);
+
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+game:
+  model: teams
+  teams: 2
+  cooldown:
+    picks: 2
+  choices:
+    - name: Alice
+      cooling:
+        picks: 2
+    - name: Bob
+      cooling:
+        picks: 2
+    - name: Carol
+      cooling:
+        picks: 2
+    - name: Dave
+      cooling:
+        picks: 2
+    - name: Erin
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
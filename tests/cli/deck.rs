@@ -0,0 +1,78 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the deck model.
+use std::collections::{BTreeMap, HashSet};
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+deck:
+  model: deck
+  choices:
+    - name: option 1
+      drawn: true
+    - name: option 2
+    - name: option 3
+      drawn: false
+";
+
+#[test]
+// Assert correct behavior with a deck model config
+fn pick() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["deck"], "y\n", true);
+
+    // Assert that the chosen item was still in the deck. Note that "option 1" is not listed here,
+    // since it has already been drawn.
+    let expected_values: HashSet<&'static str> = ["option 2", "option 3"].iter().cloned().collect();
+    let pick = super::get_pick(&stdout);
+    assert!(expected_values.contains(pick.as_str()));
+    // Assert that the deck model marks the picked item as drawn
+    let mut expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
+    if let ConfigCategory::Deck { choices, .. } = &mut expected_config.get_mut("deck").unwrap() {
+        let index = choices.iter().position(|x| x.name == pick).unwrap();
+        choices[index].drawn = true;
+    }
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that the deck is reshuffled once every choice has been drawn
+fn pick_reshuffle() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["deck", "--count", "3"], "y\ny\ny\n", true);
+
+    assert!(stdout.contains("The deck is empty, so it has been reshuffled.\n"));
+    // The last pick came from the fresh deck, so it should be the only choice marked as drawn.
+    let picks = super::get_picks(&stdout);
+    assert_eq!(picks.len(), 3);
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    if let ConfigCategory::Deck { choices, .. } = &parsed_config["deck"] {
+        let drawn = choices
+            .iter()
+            .filter(|c| c.drawn)
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(drawn, vec![picks[2].clone()]);
+    } else {
+        panic!("The deck category should still be a deck.");
+    }
+}
@@ -0,0 +1,73 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the dice model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that the dice are rolled, and that the verbose table shows the chance of each total
+fn pick() {
+    let config = "
+---
+damage:
+  model: dice
+  roll: 2d6+1
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["damage", "-v"], "y\n", true);
+
+    let total: i64 = super::get_pick(&stdout).parse().unwrap();
+    assert!((3..=13).contains(&total));
+    assert!(stdout.contains("16.67%"));
+    // The dice model doesn't keep any state.
+    let expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(config).expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that a roll that can't be parsed is reported
+fn pick_invalid() {
+    let config = "
+---
+damage:
+  model: dice
+  roll: 2d6+1d
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["damage"], "", false);
+
+    assert!(stdout.contains("The dice roll `2d6+1d` of the category `damage`"));
+}
+
+#[test]
+// Assert that a roll with too many dice is reported, even if the count doesn't fit in a number
+fn pick_too_many_dice() {
+    let config = "
+---
+damage:
+  model: dice
+  roll: 18446744073709551615d6
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["damage"], "", false);
+
+    assert!(stdout.contains("The dice roll `18446744073709551615d6` of the category `damage`"));
+}
@@ -42,6 +42,83 @@ fn category_not_found() {
     assert_eq!(config_contents, CATEGORY_NOT_FOUND_CONFIG);
 }
 
+#[test]
+// The user should get a useful error message if they ask for more items than the category has.
+fn not_enough_choices() {
+    let expected_output = "Cannot pick 4 distinct choices from the category `test`, which only \
+                           has 3 available.\n";
+
+    let (stdout, config_contents) = super::test_rpick_with_config(
+        CATEGORY_NOT_FOUND_CONFIG,
+        &mut ["test", "-n", "4"],
+        "",
+        false,
+    );
+
+    assert_eq!(stdout, expected_output);
+    // Since the pick failed, rpick should not have changed the file.
+    assert_eq!(config_contents, CATEGORY_NOT_FOUND_CONFIG);
+}
+
+#[test]
+// Asking for no items at all should be rejected, rather than quietly doing nothing.
+fn zero_count() {
+    let (stdout, config_contents) = super::test_rpick_with_config(
+        CATEGORY_NOT_FOUND_CONFIG,
+        &mut ["test", "-n", "0"],
+        "",
+        false,
+    );
+
+    // clap reports the error on stderr.
+    assert_eq!(stdout, "");
+    assert_eq!(config_contents, CATEGORY_NOT_FOUND_CONFIG);
+}
+
+#[test]
+// --count has no meaning with --order or --tournament, so it should be rejected rather than ignored.
+fn count_conflicts() {
+    for flag in [&["--order"][..], &["--tournament", "1"][..]] {
+        let mut args = vec!["test", "-n", "2"];
+        args.extend_from_slice(flag);
+
+        let (stdout, config_contents) =
+            super::test_rpick_with_config(CATEGORY_NOT_FOUND_CONFIG, &mut args, "", false);
+
+        // clap reports the error on stderr.
+        assert_eq!(stdout, "");
+        assert_eq!(config_contents, CATEGORY_NOT_FOUND_CONFIG);
+    }
+}
+
+const CATEGORY_CYCLE_CONFIG: &str = "
+---
+dinner:
+  model: even
+  choices:
+    - name: restaurant
+      category: restaurants
+restaurants:
+  model: even
+  choices:
+    - name: surprise me
+      category: dinner
+";
+
+#[test]
+// The user should get a useful error message if the categories refer to each other in a cycle.
+fn category_cycle() {
+    let expected_output =
+        "The categories refer to each other in a cycle: dinner → restaurants → dinner.\n";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CATEGORY_CYCLE_CONFIG, &mut ["dinner"], "", false);
+
+    assert_eq!(stdout, expected_output);
+    // Since the pick failed, rpick should not have changed the file.
+    assert_eq!(config_contents, CATEGORY_CYCLE_CONFIG);
+}
+
 #[test]
 // Assert correct behavior when the config file is not found.
 fn config_not_found() {
@@ -44,16 +44,72 @@ fn pick() {
     // Assert that the gaussian model moves the picked item into last place
     let mut expected_config: BTreeMap<String, ConfigCategory> =
         serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
-    if let ConfigCategory::Gaussian {
-        choices,
-        stddev_scaling_factor: _,
-    } = &mut expected_config.get_mut("gaussian").unwrap()
+    if let ConfigCategory::Gaussian { choices, .. } =
+        &mut expected_config.get_mut("gaussian").unwrap()
     {
-        let index = choices.iter().position(|x| x == pick.as_str()).unwrap();
+        let index = choices.iter().position(|x| x.name == pick).unwrap();
         choices.remove(index);
-        choices.push(pick);
+        choices.push(pick.into());
     }
     let parsed_config: BTreeMap<String, ConfigCategory> =
         serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
     assert_eq!(parsed_config, expected_config);
 }
+
+#[test]
+// Assert that the mean and last_item_probability settings can favor the middle of the list
+fn pick_mean() {
+    let config = "
+---
+gaussian:
+  model: gaussian
+  mean: 1.5
+  last_item_probability: 0.000000001
+  choices:
+    - option 1
+    - option 2
+    - option 3
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["gaussian"], "y\n", true);
+
+    assert_eq!(super::get_pick(&stdout), "option 2");
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+gaussian:
+  model: gaussian
+  mean: 1.5
+  last_item_probability: 0.000000001
+  choices:
+    - option 1
+    - option 3
+    - option 2
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that a standard deviation scaling factor of 0 is reported rather than crashing
+fn pick_invalid() {
+    let config = "
+---
+gaussian:
+  model: gaussian
+  stddev_scaling_factor: 0
+  choices:
+    - option 1
+    - option 2
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["gaussian"], "", false);
+
+    assert!(stdout.contains(
+        "The category `gaussian` must have a stddev_scaling_factor that is larger than 0"
+    ));
+}
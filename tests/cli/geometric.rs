@@ -0,0 +1,79 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the geometric model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that a p of 1 always picks the first item, and that the picked item is moved into last
+// place
+fn pick() {
+    let config = "
+---
+geometric:
+  model: geometric
+  p: 1.0
+  choices:
+    - option 1
+    - option 2
+    - option 3
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["geometric"], "y\n", true);
+
+    assert_eq!(super::get_pick(&stdout), "option 1");
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+geometric:
+  model: geometric
+  p: 1.0
+  choices:
+    - option 2
+    - option 3
+    - option 1
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that an invalid p is reported rather than picked with
+fn pick_invalid_p() {
+    let config = "
+---
+geometric:
+  model: geometric
+  p: 1.5
+  choices:
+    - option 1
+    - option 2
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["geometric"], "", false);
+
+    assert_eq!(
+        stdout,
+        "The category `geometric` must have a p between 0 and 1.\n"
+    );
+    assert_eq!(config_contents, config);
+}
@@ -51,7 +51,7 @@ fn pick() {
     // Assert that the inventory model reduces the tickets on the picked item
     let mut expected_config: BTreeMap<String, ConfigCategory> =
         serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
-    if let ConfigCategory::Inventory { choices } =
+    if let ConfigCategory::Inventory { choices, .. } =
         &mut expected_config.get_mut("inventory").unwrap()
     {
         let index = choices.iter().position(|x| x.name == pick).unwrap();
@@ -0,0 +1,108 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the lfu model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that the lfu model always picks the choice that has been accepted the fewest times
+fn pick() {
+    let mut config_contents = "
+---
+lfu:
+  model: lfu
+  choices:
+    - name: option 1
+      count: 2
+    - name: option 2
+    - name: option 3
+      count: 3
+"
+    .to_string();
+    let mut picks = vec![];
+
+    for _ in 0..2 {
+        let (stdout, new_config_contents) =
+            super::test_rpick_with_config(&config_contents, &mut ["lfu"], "y\n", true);
+        picks.push(super::get_pick(&stdout));
+        config_contents = new_config_contents;
+    }
+
+    assert_eq!(picks, vec!["option 2", "option 2"]);
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+lfu:
+  model: lfu
+  choices:
+    - name: option 1
+      count: 2
+    - name: option 2
+      count: 2
+    - name: option 3
+      count: 3
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that rejecting the least frequently used choice offers the next least frequently used
+// choice, and that the verbose table shows the counts
+fn pick_reject() {
+    let config = "
+---
+lfu:
+  model: lfu
+  decay: 0.5
+  choices:
+    - name: option 1
+      count: 2
+    - name: option 2
+      count: 1
+    - name: option 3
+      count: 3
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["lfu", "-v"], "n\ny\n", true);
+
+    assert_eq!(super::get_picks(&stdout), vec!["option 2", "option 1"]);
+    assert!(stdout.contains("Count"));
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+lfu:
+  model: lfu
+  decay: 0.5
+  choices:
+    - name: option 1
+      count: 2
+    - name: option 2
+      count: 0.5
+    - name: option 3
+      count: 1.5
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
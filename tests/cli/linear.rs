@@ -0,0 +1,47 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the linear model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that every picked item is moved into last place, in the order they were picked
+fn pick_count() {
+    let config = "
+---
+linear:
+  model: linear
+  choices:
+    - option 1
+    - option 2
+    - option 3
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["linear", "--count", "3"], "y\ny\ny\n", true);
+
+    let picks = super::get_picks(&stdout);
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    let ConfigCategory::Linear { choices, .. } = &parsed_config["linear"] else {
+        panic!("The category changed models.");
+    };
+    assert_eq!(
+        choices.iter().map(|c| c.name.clone()).collect::<Vec<_>>(),
+        picks
+    );
+}
@@ -0,0 +1,57 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the loot model.
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that the guaranteed items are dropped along with one item from each roll, and that the
+// verbose table shows the chance of every item
+fn pick() {
+    let config = "
+---
+chest:
+  model: loot
+  rolls: 2
+  guaranteed: [Coins]
+  choices:
+    - name: Common
+      weight: 3
+      table:
+        - name: Potion
+        - name: Torch
+    - name: Rare
+      table:
+        - name: Sword
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["chest", "-v"], "y\ny\n", true);
+
+    let re = Regex::new(r"Loot: Coins, (Potion|Torch|Sword), (Potion|Torch|Sword)\n").unwrap();
+    assert!(re.is_match(&stdout), "{}", stdout);
+    assert_eq!(super::get_picks(&stdout).len(), 2);
+    assert!(stdout.contains("Common"));
+    assert!(stdout.contains("37.50"));
+    // The loot model doesn't keep any state.
+    let expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(config).expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
@@ -59,7 +59,9 @@ fn pick() {
     // to the ones that weren't picked.
     let mut expected_config: BTreeMap<String, ConfigCategory> =
         serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
-    if let ConfigCategory::Lottery { choices } = &mut expected_config.get_mut("lottery").unwrap() {
+    if let ConfigCategory::Lottery { choices, .. } =
+        &mut expected_config.get_mut("lottery").unwrap()
+    {
         for choice in choices.iter_mut() {
             if choice.name == pick {
                 choice.tickets = choice.reset;
@@ -39,7 +39,7 @@ fn pick() {
     // Assert that the lru model moves the picked item into last place
     let mut expected_config: BTreeMap<String, ConfigCategory> =
         serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
-    if let ConfigCategory::Lru { choices } = &mut expected_config.get_mut("lru").unwrap() {
+    if let ConfigCategory::Lru { choices, .. } = &mut expected_config.get_mut("lru").unwrap() {
         let pick = choices.remove(0);
         choices.push(pick);
     }
@@ -47,3 +47,31 @@ fn pick() {
         serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
     assert_eq!(parsed_config, expected_config);
 }
+
+#[test]
+// Assert that the lru model moves every picked item into last place, in the order they were picked
+fn pick_count() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["lru", "--count", "2"], "n\ny\ny\n", true);
+
+    assert_eq!(
+        stdout,
+        "Choice is option 1. Accept? (Y/n) Choice is option 2. Accept? (Y/n) Choice is option 1. \
+         Accept? (Y/n) "
+    );
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+lru:
+  model: lru
+  choices:
+    - option 3
+    - option 2
+    - option 1
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
@@ -0,0 +1,54 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the markov model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+workout:
+  model: markov
+  last: legs
+  choices:
+    - name: legs
+      transitions:
+        legs: 0
+    - name: arms
+      transitions:
+        arms: 0
+";
+
+#[test]
+// Assert that the next pick follows the transitions of the last pick, and that the last pick is
+// remembered
+fn pick() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["workout"], "y\n", true);
+    assert_eq!(stdout, "Choice is arms. Accept? (Y/n) ");
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(&config_contents, &mut ["workout"], "y\n", true);
+    assert_eq!(stdout, "Choice is legs. Accept? (Y/n) ");
+    let mut expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
+    if let ConfigCategory::Markov { last, .. } = expected_config.get_mut("workout").unwrap() {
+        *last = Some("legs".to_string());
+    }
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
@@ -22,13 +22,38 @@ use assert_cmd::cargo::cargo_bin_cmd;
 use regex::Regex;
 use tempfile::NamedTempFile;
 
+mod bandit;
+mod bundle;
+mod composite;
+mod cooldown;
+mod deck;
+mod dice;
 mod error_handling;
 mod even;
 mod gaussian;
+mod geometric;
 mod inventory;
+mod lfu;
+mod linear;
+mod loot;
 mod lottery;
 mod lru;
+mod markov;
+mod nested;
+mod order;
+mod pairs;
+mod quota;
+mod range;
+mod rated;
+mod recency;
+mod schedule;
+mod secret_santa;
+mod spaced_repetition;
+mod stratified;
+mod tags;
+mod teams;
 mod weighted;
+mod zipf;
 
 // Return which item rpick chose in the given stdout.
 //
@@ -45,6 +70,22 @@ fn get_pick(stdout: &str) -> String {
     captures.name("pick").unwrap().as_str().to_string()
 }
 
+// Return every item that rpick offered in the given stdout, in the order they were offered.
+//
+// # Arguments
+//
+// * `stdout` - The output from an rpick run.
+//
+// # Returns
+//
+// The items that rpick offered.
+fn get_picks(stdout: &str) -> Vec<String> {
+    let re = Regex::new(r"Choice is (?P<pick>[^.]*)\.").unwrap();
+    re.captures_iter(stdout)
+        .map(|c| c.name("pick").unwrap().as_str().to_string())
+        .collect()
+}
+
 // Run rpick with the given config, arguments, and stdin.
 //
 // # Arguments
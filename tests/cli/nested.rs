@@ -0,0 +1,78 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of choices that refer to other categories.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+dinner:
+  model: lru
+  choices:
+    - name: restaurant
+      category: restaurants
+    - cook
+restaurants:
+  model: lru
+  choices:
+    - Sitti
+    - Bida Manda
+";
+
+#[test]
+// Assert that accepting a choice that refers to another category picks from that category too
+fn pick() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["dinner"], "y\nn\ny\n", true);
+
+    assert_eq!(
+        stdout,
+        "Choice is restaurant. Accept? (Y/n) Choice is Sitti. Accept? (Y/n) Choice is Bida Manda. \
+         Accept? (Y/n) Picked: restaurant → Bida Manda\n"
+    );
+    // Both categories should have been updated.
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+dinner:
+  model: lru
+  choices:
+    - cook
+    - name: restaurant
+      category: restaurants
+restaurants:
+  model: lru
+  choices:
+    - Sitti
+    - Bida Manda
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that choices without a category are written back to the config as plain strings
+fn pick_round_trip() {
+    let (_, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["restaurants"], "y\n", true);
+
+    assert!(config_contents.contains("- Sitti\n"));
+    assert!(config_contents.contains("category: restaurants\n"));
+}
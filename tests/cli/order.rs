@@ -0,0 +1,125 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the order operation.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+lru:
+  model: lru
+  choices:
+    - option 1
+    - option 2
+    - option 3
+";
+
+#[test]
+// Assert that every choice is printed in a numbered list, and that the config isn't changed
+fn order() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["lru", "--order"], "", true);
+
+    assert_eq!(stdout, "1. option 1\n2. option 2\n3. option 3\n");
+    let expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that a weighted category is put in an order that includes every choice
+fn order_weighted() {
+    let config = "
+---
+weighted:
+  model: weighted
+  choices:
+    - name: option 1
+    - name: option 2
+      weight: 5
+    - name: option 3
+      weight: 0
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["weighted", "--order"], "", true);
+
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    // The choice that has no chance of being picked always goes last.
+    assert_eq!(lines[2], "3. option 3");
+    assert!(lines.contains(&"1. option 1") || lines.contains(&"2. option 1"));
+}
+
+#[test]
+// Assert that --update moves every choice of an lru category to the end, in order
+fn order_update() {
+    let config = "
+---
+lru:
+  model: lru
+  choices:
+    - name: option 1
+      tags: [skip]
+    - option 2
+    - option 3
+";
+
+    let (stdout, config_contents) = super::test_rpick_with_config(
+        config,
+        &mut ["lru", "--order", "--update", "-T", "skip"],
+        "",
+        true,
+    );
+
+    assert_eq!(stdout, "1. option 2\n2. option 3\n");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    let ConfigCategory::Lru { choices, .. } = &parsed_config["lru"] else {
+        panic!("The category should still be an lru category.");
+    };
+    assert_eq!(
+        choices.iter().map(|c| &c.name[..]).collect::<Vec<_>>(),
+        vec!["option 1", "option 2", "option 3"]
+    );
+}
+
+#[test]
+// Assert that a model that can't be ordered gives an error
+fn order_cannot_order() {
+    let config = "
+---
+dice:
+  model: dice
+  roll: 2d6
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["dice", "--order"], "", false);
+
+    assert_eq!(
+        stdout,
+        "The category `dice` cannot be ordered, since only even, gaussian, inventory, lottery, lru, \
+         and weighted categories can be ordered.\n"
+    );
+}
+
+#[test]
+// Assert that --update can't be used without --order
+fn update_requires_order() {
+    super::test_rpick_with_config(CONFIG, &mut ["lru", "--update"], "", false);
+}
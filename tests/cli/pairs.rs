@@ -0,0 +1,125 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the pairs model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that the choices are paired up, that the odd one out sits out, and that the pairs are
+// recorded once they are accepted
+fn pick() {
+    let config = "
+---
+devs:
+  model: pairs
+  choices:
+    - name: Alice
+      sat_out: 1
+    - name: Bob
+      sat_out: 1
+    - name: Carol
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["devs", "-v"], "y\n", true);
+
+    // Carol is the only one who hasn't sat out yet.
+    assert_eq!(super::get_pick(&stdout), "Alice & Bob; Carol sits out");
+    assert!(stdout.contains("Sat Out"));
+    let expected_config = "
+---
+devs:
+  model: pairs
+  rounds: 1
+  choices:
+    - name: Alice
+      last_paired:
+        Bob: 1
+      sat_out: 1
+    - name: Bob
+      last_paired:
+        Alice: 1
+      sat_out: 1
+    - name: Carol
+      sat_out: 1
+";
+    let expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(expected_config).expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that only the accepted pairs are recorded
+fn pick_reject() {
+    let config = "
+---
+devs:
+  model: pairs
+  rounds: 4
+  choices:
+    - name: Alice
+    - name: Bob
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["devs"], "n\ny\n", true);
+
+    assert_eq!(super::get_picks(&stdout), ["Alice & Bob", "Alice & Bob"]);
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    let ConfigCategory::Pairs {
+        rounds, choices, ..
+    } = &parsed_config["devs"]
+    else {
+        panic!("The category should still be a pairs category.");
+    };
+    assert_eq!(*rounds, 5);
+    assert_eq!(
+        choices[0].last_paired,
+        BTreeMap::from([("Bob".to_string(), 5)])
+    );
+}
+
+#[test]
+// Assert that a choice that refers to another category is rejected, since the pairs model doesn't
+// pick a single choice that could be followed
+fn pick_nested() {
+    let config = "
+---
+devs:
+  model: pairs
+  choices:
+    - name: Alice
+    - name: Bob
+      category: testers
+testers:
+  model: even
+  choices:
+    - Carol
+";
+
+    let (stdout, config_contents) = super::test_rpick_with_config(config, &mut ["devs"], "", false);
+
+    assert_eq!(
+        stdout,
+        "The choice `Bob` of the category `devs` can't refer to another category, because the \
+         pairs model doesn't pick a single choice.\n"
+    );
+    assert_eq!(config_contents, config);
+}
@@ -0,0 +1,87 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the quota model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+quota:
+  model: quota
+  choices:
+    - name: Alice
+      target: 2
+    - name: Bob
+      target: 3
+";
+
+#[test]
+// Assert that the quota model keeps the picks close to the target shares
+fn pick() {
+    let mut config_contents = CONFIG.to_string();
+    let mut picks = vec![];
+
+    for _ in 0..5 {
+        let (stdout, new_config_contents) =
+            super::test_rpick_with_config(&config_contents, &mut ["quota"], "y\n", true);
+        picks.push(super::get_pick(&stdout));
+        config_contents = new_config_contents;
+    }
+
+    assert_eq!(picks, vec!["Bob", "Alice", "Bob", "Alice", "Bob"]);
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+quota:
+  model: quota
+  choices:
+    - name: Alice
+      target: 2
+      picks: 2
+    - name: Bob
+      target: 3
+      picks: 3
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that a negative tolerance is reported rather than crashing
+fn pick_negative_tolerance() {
+    let config = "
+---
+quota:
+  model: quota
+  tolerance: -1
+  choices:
+    - name: Alice
+      target: 2
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["quota"], "", false);
+
+    assert_eq!(
+        stdout,
+        "The category `quota` must have a tolerance that is a finite number of at least 0.\n"
+    );
+    assert_eq!(config_contents, config);
+}
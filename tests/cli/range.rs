@@ -0,0 +1,101 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the integer range and float range models.
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that a number is picked from the steps of an integer range
+fn pick_integer() {
+    let config = "
+---
+minutes:
+  model: integer_range
+  min: 10
+  max: 60
+  step: 5
+  distribution:
+    shape: normal
+    stddev: 10
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["minutes", "-v"], "y\n", true);
+
+    let minutes: i64 = super::get_pick(&stdout).parse().unwrap();
+    assert!((10..=60).contains(&minutes));
+    assert_eq!(minutes % 5, 0);
+    assert!(stdout.contains("19.86%"));
+    // The integer range model doesn't keep any state.
+    let expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(config).expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that a number is picked from anywhere in a float range, and that rejecting a number picks
+// another one
+fn pick_float() {
+    let config = "
+---
+weight:
+  model: float_range
+  min: 0.5
+  max: 2.5
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["weight"], "n\ny\n", true);
+
+    // get_picks() stops at the decimal point, so we match the whole numbers here.
+    let re = Regex::new(r"Choice is (?P<pick>\d\.\d{2})\. Accept").unwrap();
+    let picks = re
+        .captures_iter(&stdout)
+        .map(|c| c.name("pick").unwrap().as_str().parse::<f64>().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(picks.len(), 2);
+    assert!(picks.iter().all(|p| (0.5..=2.5).contains(p)));
+}
+
+#[test]
+// Assert that a normal distribution whose mean is too far from the range is rejected
+fn pick_far_mean() {
+    let config = "
+---
+minutes:
+  model: integer_range
+  min: 0
+  max: 10
+  distribution:
+    shape: normal
+    mean: 1000
+    stddev: 1
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["minutes", "-v"], "y\n", false);
+
+    assert_eq!(
+        stdout,
+        "The category `minutes` must have a minimum that isn't larger than its maximum, a step and \
+         standard deviation that are larger than 0, and a mean that is near enough to the range to \
+         reach it.\n"
+    );
+    assert_eq!(config_contents, config);
+}
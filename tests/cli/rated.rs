@@ -0,0 +1,124 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the rated model and tournament mode.
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that a tournament turns a weighted category into a rated one and moves the ratings
+fn tournament() {
+    let config = "
+---
+food:
+  model: weighted
+  choices:
+    - name: pizza
+    - name: tacos
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["food", "--tournament", "1"], "1\n", true);
+
+    let re = Regex::new(r"Do you prefer \(1\) (?P<first>\w+) or \(2\) (?P<second>\w+)\? \(1/2\) ")
+        .unwrap();
+    let captures = re.captures(&stdout).expect("The user was not prompted");
+    let winner = captures.name("first").unwrap().as_str();
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    let ConfigCategory::Rated { choices, .. } = &parsed_config["food"] else {
+        panic!("The category was not converted to the rated model.");
+    };
+    assert_eq!(choices.len(), 2);
+    for choice in choices {
+        let expected_rating = if choice.name == winner {
+            1516.0
+        } else {
+            1484.0
+        };
+        assert_eq!(choice.rating, expected_rating);
+        assert_eq!(choice.comparisons, 1);
+    }
+}
+
+#[test]
+// Assert that the user is asked again if they don't answer with 1 or 2, and that the answer may be
+// surrounded by spaces
+fn tournament_reprompt() {
+    let config = "
+---
+food:
+  model: weighted
+  choices:
+    - name: pizza
+    - name: tacos
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["food", "--tournament", "1"], "3\n 2 \n", true);
+
+    let re = Regex::new(r"Do you prefer \(1\) (?P<first>\w+) or \(2\) (?P<second>\w+)\? \(1/2\) ")
+        .unwrap();
+    assert_eq!(re.find_iter(&stdout).count(), 2);
+    let winner = re
+        .captures(&stdout)
+        .unwrap()
+        .name("second")
+        .unwrap()
+        .as_str();
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    let ConfigCategory::Rated { choices, .. } = &parsed_config["food"] else {
+        panic!("The category was not converted to the rated model.");
+    };
+    for choice in choices {
+        let expected_rating = if choice.name == winner {
+            1516.0
+        } else {
+            1484.0
+        };
+        assert_eq!(choice.rating, expected_rating);
+    }
+}
+
+#[test]
+// Assert that the rated model never picks a choice with a zero rating
+fn pick() {
+    let config = "
+---
+food:
+  model: rated
+  choices:
+    - name: pizza
+      rating: 1600
+    - name: tacos
+      rating: 0
+";
+
+    for _ in 0..5 {
+        let (stdout, config_contents) =
+            super::test_rpick_with_config(config, &mut ["food"], "y\n", true);
+
+        assert_eq!(super::get_pick(&stdout), "pizza");
+        // The rated model doesn't change the config when picking.
+        let parsed_config: BTreeMap<String, ConfigCategory> =
+            serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+        let expected_config: BTreeMap<String, ConfigCategory> =
+            serde_yaml::from_str(config).expect("Could not parse yaml");
+        assert_eq!(parsed_config, expected_config);
+    }
+}
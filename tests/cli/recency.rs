@@ -0,0 +1,98 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the recency model.
+use std::collections::{BTreeMap, HashSet};
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+recency:
+  model: recency
+  curve:
+    shape: exponential
+    doubling_days: 3.5
+  choices:
+    - name: option 1
+      last_picked: 2020-01-01T12:00:00-05:00
+    - name: option 2
+      last_picked: 2021-06-01T18:30:00+02:00
+    - name: option 3
+";
+
+#[test]
+// Assert correct behavior with a recency model config
+fn pick() {
+    let before = chrono::Local::now();
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["recency"], "y\n", true);
+
+    // Assert that the chosen item was a member of the config
+    let expected_values: HashSet<&'static str> = ["option 1", "option 2", "option 3"]
+        .iter()
+        .cloned()
+        .collect();
+    let pick = super::get_pick(&stdout);
+    assert!(expected_values.contains(pick.as_str()));
+    // Assert that the recency model sets the picked item's last_picked time to now, and leaves the
+    // others alone.
+    let mut expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(CONFIG).expect("Could not parse yaml");
+    let mut parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    if let (
+        ConfigCategory::Recency { choices, .. },
+        ConfigCategory::Recency {
+            choices: parsed_choices,
+            ..
+        },
+    ) = (
+        &mut expected_config.get_mut("recency").unwrap(),
+        &mut parsed_config.get_mut("recency").unwrap(),
+    ) {
+        let index = choices.iter().position(|x| x.name == pick).unwrap();
+        let last_picked = parsed_choices[index].last_picked.unwrap();
+        assert!(last_picked >= before - chrono::Duration::seconds(1));
+        assert!(last_picked <= chrono::Local::now());
+        choices[index].last_picked = Some(last_picked);
+    }
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that a curve that would give the choices no weight is reported rather than crashing
+fn pick_invalid_curve() {
+    let config = "
+---
+recency:
+  model: recency
+  curve:
+    shape: capped
+    max_days: -5
+  choices:
+    - name: option 1
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["recency"], "", false);
+
+    assert_eq!(
+        stdout,
+        "The category `recency` must have a doubling_days that is larger than 0, or a max_days of \
+         at least 0.\n"
+    );
+    assert_eq!(config_contents, config);
+}
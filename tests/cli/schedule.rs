@@ -0,0 +1,80 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of choice schedules.
+const CONFIG: &str = "
+---
+lru:
+  model: lru
+  choices:
+    - name: brunch
+      schedule:
+        days: [sat, sun]
+        times:
+          - start: '09:00'
+            end: '14:00'
+    - name: late night
+      schedule:
+        times:
+          - start: '22:00'
+            end: '03:00'
+    - name: summer picnic
+      schedule:
+        dates:
+          - start: 2026-06-01
+            end: 2026-08-31
+";
+
+#[test]
+// Assert that only the choices that are available at the given time are offered
+fn pick_at() {
+    // 2026-10-17 is a Saturday.
+    let (stdout, _) = super::test_rpick_with_config(
+        CONFIG,
+        &mut ["lru", "--at", "2026-10-17 10:30"],
+        "y\n",
+        true,
+    );
+    assert_eq!(stdout, "Choice is brunch. Accept? (Y/n) ");
+
+    let (stdout, _) = super::test_rpick_with_config(
+        CONFIG,
+        &mut ["lru", "--at", "2026-10-17T01:00"],
+        "y\n",
+        true,
+    );
+    assert_eq!(stdout, "Choice is late night. Accept? (Y/n) ");
+
+    let (stdout, _) = super::test_rpick_with_config(
+        CONFIG,
+        &mut ["lru", "--at", "2026-07-15 12:00"],
+        "y\n",
+        true,
+    );
+    assert_eq!(stdout, "Choice is summer picnic. Accept? (Y/n) ");
+}
+
+#[test]
+// The user should get a useful error message if nothing is available at the given time
+fn nothing_available() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["lru", "--at", "2026-10-14 12:00"], "", false);
+
+    assert_eq!(
+        stdout,
+        "Cannot pick 1 distinct choices from the category `lru`, which only has 0 available.\n"
+    );
+    assert_eq!(config_contents, CONFIG);
+}
@@ -0,0 +1,86 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the secret santa model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+santa:
+  model: secret_santa
+  choices:
+    - name: Alice
+    - name: Bob
+";
+
+#[test]
+// Assert that the assignment is offered to the user and recorded in the history, and that the
+// next round can't repeat it
+fn pick() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["santa"], "y\n", true);
+
+    assert_eq!(super::get_pick(&stdout), "Alice → Bob, Bob → Alice");
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+santa:
+  model: secret_santa
+  avoid_rounds: 1
+  history:
+    - Alice: Bob
+      Bob: Alice
+  choices:
+    - name: Alice
+    - name: Bob
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+
+    let (stdout, _) = super::test_rpick_with_config(&config_contents, &mut ["santa"], "", false);
+
+    assert_eq!(
+        stdout,
+        "No assignment of the category `santa` avoids all of its exclusions and recent history.\n"
+    );
+}
+
+#[test]
+// Assert that exclusions are respected
+fn pick_exclude() {
+    let config = "
+---
+santa:
+  model: secret_santa
+  choices:
+    - name: Alice
+      exclude: [Bob]
+    - name: Bob
+    - name: Carol
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["santa", "-v"], "y\n", true);
+
+    assert!(stdout.contains("Giver"));
+    assert_eq!(
+        super::get_pick(&stdout),
+        "Alice → Carol, Bob → Alice, Carol → Bob"
+    );
+}
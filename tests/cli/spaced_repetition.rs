@@ -0,0 +1,92 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the spaced repetition model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that accepting a choice asks for a grade until it gets one, and reschedules the choice
+fn pick() {
+    let config = "
+---
+guitar:
+  model: spaced_repetition
+  choices:
+    - name: scales
+      repetitions: 1
+      interval: 1
+      due: 2026-10-15T12:00:00+00:00
+";
+
+    let (stdout, config_contents) = super::test_rpick_with_config(
+        config,
+        &mut ["guitar", "--at", "2026-10-16 12:00"],
+        "y\nfive\n4\n",
+        true,
+    );
+
+    assert_eq!(super::get_pick(&stdout), "scales");
+    assert_eq!(stdout.matches("How well did you recall scales?").count(), 2);
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    let ConfigCategory::SpacedRepetition { choices, .. } = &parsed_config["guitar"] else {
+        panic!("The category should still be a spaced repetition category.");
+    };
+    assert_eq!(choices[0].repetitions, 2);
+    assert_eq!(choices[0].interval, 6);
+    assert!((choices[0].ease - 2.5).abs() < 0.000_001);
+    assert_eq!(
+        choices[0].due.unwrap().naive_local().to_string(),
+        "2026-10-22 12:00:00"
+    );
+}
+
+#[test]
+// Assert that rejecting a choice doesn't ask for a grade or change the choice
+fn pick_reject() {
+    let config = "
+---
+guitar:
+  model: spaced_repetition
+  choices:
+    - name: scales
+    - name: chords
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["guitar"], "n\ny\n0\n", true);
+
+    let picks = super::get_picks(&stdout);
+    assert_eq!(picks.len(), 2);
+    assert_ne!(picks[0], picks[1]);
+    assert_eq!(stdout.matches("How well did you recall").count(), 1);
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    let ConfigCategory::SpacedRepetition { choices, .. } = &parsed_config["guitar"] else {
+        panic!("The category should still be a spaced repetition category.");
+    };
+    for choice in choices {
+        if choice.name == picks[1] {
+            assert_eq!(choice.interval, 1);
+            assert!((choice.ease - 1.7).abs() < 0.000_001);
+            assert!(choice.due.is_some());
+        } else {
+            assert_eq!(choice.interval, 0);
+            assert_eq!(choice.due, None);
+        }
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the stratified model.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that one item is picked from each group, in order, and that each group's state is
+// updated on its own
+fn pick() {
+    let config = "
+---
+meal:
+  model: stratified
+  groups:
+    - name: protein
+      model: lru
+      choices:
+        - tofu
+        - chicken
+    - name: starch
+      model: lottery
+      choices:
+        - name: rice
+          tickets: 1
+        - name: pasta
+          tickets: 0
+    - name: vegetable
+      model: inventory
+      choices:
+        - name: broccoli
+          tickets: 2
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["meal"], "n\ny\ny\ny\n", true);
+
+    assert_eq!(
+        super::get_picks(&stdout),
+        vec!["tofu", "chicken", "rice", "broccoli"]
+    );
+    assert!(stdout.ends_with("Picked: protein: chicken; starch: rice; vegetable: broccoli\n"));
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+meal:
+  model: stratified
+  groups:
+    - name: protein
+      model: lru
+      choices:
+        - tofu
+        - chicken
+    - name: starch
+      model: lottery
+      choices:
+        - name: rice
+          tickets: 0
+        - name: pasta
+          tickets: 1
+    - name: vegetable
+      model: inventory
+      choices:
+        - name: broccoli
+          tickets: 1
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that the tag filter applies to every group, and that the items of a group can refer to
+// other categories
+fn pick_tags_and_nested() {
+    let config = "
+---
+meal:
+  model: stratified
+  groups:
+    - name: protein
+      model: lru
+      choices:
+        - name: chicken
+          tags: [meat]
+        - tofu
+    - name: vegetable
+      model: lru
+      choices:
+        - name: salad
+          category: salads
+salads:
+  model: lru
+  choices:
+    - name: caesar
+      tags: [meat]
+";
+
+    let (stdout, _) =
+        super::test_rpick_with_config(config, &mut ["meal", "-T", "meat"], "y\ny\ny\n", true);
+
+    // The categories that items refer to aren't filtered by tags.
+    assert_eq!(super::get_picks(&stdout), vec!["tofu", "salad", "caesar"]);
+}
+
+#[test]
+// Assert that an error in one of the groups names the group
+fn pick_empty_group() {
+    let config = "
+---
+meal:
+  model: stratified
+  groups:
+    - name: protein
+      model: lru
+      choices:
+        - name: chicken
+          tags: [meat]
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["meal", "-T", "meat"], "", false);
+
+    assert_eq!(
+        stdout,
+        "Cannot pick 1 distinct choices from the category `meal → protein`, which only has 0 \
+         available.\n"
+    );
+}
@@ -0,0 +1,92 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the tag filters.
+use std::collections::BTreeMap;
+
+use rpick::config::ConfigCategory;
+
+const CONFIG: &str = "
+---
+lru:
+  model: lru
+  choices:
+    - name: option 1
+      tags: [late]
+    - option 2
+    - name: option 3
+      tags: [vegetarian]
+    - name: option 4
+      tags: [vegetarian, late]
+";
+
+#[test]
+// Assert that only the choices with the requested tag are offered
+fn tag() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["lru", "--tag", "vegetarian"], "n\ny\n", true);
+
+    assert_eq!(
+        stdout,
+        "Choice is option 3. Accept? (Y/n) Choice is option 4. Accept? (Y/n) "
+    );
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+lru:
+  model: lru
+  choices:
+    - name: option 1
+      tags: [late]
+    - option 2
+    - name: option 3
+      tags: [vegetarian]
+    - name: option 4
+      tags: [vegetarian, late]
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that choices with an excluded tag are not offered, and keep their place in the list
+fn not_tag() {
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(CONFIG, &mut ["lru", "-T", "late"], "y\n", true);
+
+    assert_eq!(stdout, "Choice is option 2. Accept? (Y/n) ");
+    let expected_config: BTreeMap<String, ConfigCategory> = serde_yaml::from_str(
+        "
+---
+lru:
+  model: lru
+  choices:
+    - name: option 1
+      tags: [late]
+    - name: option 3
+      tags: [vegetarian]
+    - name: option 4
+      tags: [vegetarian, late]
+    - option 2
+",
+    )
+    .expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
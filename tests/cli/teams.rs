@@ -0,0 +1,80 @@
+/*
+ * Copyright © 2026 Randy Barlow
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, version 3 of the License.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ *
+ * You should have received a copy of the GNU General Public License
+ * along with this program.  If not, see <http://www.gnu.org/licenses/>.
+ */
+/// Assert correct operation of the teams model.
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use rpick::config::ConfigCategory;
+
+#[test]
+// Assert that the choices are split into teams of the requested size, with a table column for each
+// team
+fn pick() {
+    let config = "
+---
+game:
+  model: teams
+  team_size: 2
+  choices:
+    - name: Alice
+    - name: Bob
+    - name: Carol
+    - name: Dave
+    - name: Erin
+    - name: Frank
+";
+
+    let (stdout, config_contents) =
+        super::test_rpick_with_config(config, &mut ["game", "-v"], "y\n", true);
+
+    let re = Regex::new(r"^Team 1: \w+, \w+; Team 2: \w+, \w+; Team 3: \w+, \w+$").unwrap();
+    assert!(re.is_match(&super::get_pick(&stdout)));
+    assert!(stdout.contains("Team 3"));
+    // The teams model doesn't keep any state, and doesn't write out the default skill.
+    assert!(!config_contents.contains("skill"));
+    let expected_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(config).expect("Could not parse yaml");
+    let parsed_config: BTreeMap<String, ConfigCategory> =
+        serde_yaml::from_str(&config_contents).expect("Could not parse yaml");
+    assert_eq!(parsed_config, expected_config);
+}
+
+#[test]
+// Assert that choices that must be kept together end up on the same team
+fn pick_together() {
+    let config = "
+---
+game:
+  model: teams
+  teams: 2
+  together:
+    - [Alice, Bob]
+  choices:
+    - name: Alice
+    - name: Bob
+    - name: Carol
+    - name: Dave
+";
+
+    let (stdout, _) = super::test_rpick_with_config(config, &mut ["game"], "y\n", true);
+
+    let pick = super::get_pick(&stdout);
+    assert!(
+        pick.contains("Alice, Bob"),
+        "Alice and Bob were split up: {}",
+        pick
+    );
+}